@@ -1,18 +1,32 @@
-use std::collections::HashMap;
-use std::path::{PathBuf};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::{env, fs};
 use std::fs::{File};
-use std::io::{Result, stdin, stdout, Write};
-use std::str::{FromStr};
-use chrono::{DateTime, FixedOffset, ParseResult, Utc};
+use std::io::{Cursor, IsTerminal, Read, Result, Seek, stdin, stdout, Write};
+use std::process::ExitCode;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use chrono::{DateTime, Utc};
 use colored::{ColoredString, Colorize};
-use http::{HeaderName, HeaderValue};
+use http::HeaderMap;
 use fancy_regex::Regex;
 use reqwest::{blocking};
 use uuid::Uuid;
-use serde::{Deserialize, Deserializer, Serialize};
+use versions::{Requirement, Versioning};
+use serde::{Deserialize, Serialize};
 use reqwest::blocking::{RequestBuilder, Response};
 use serde_json::Value;
+use sha2::{Digest, Sha256, Sha512};
+use zip::ZipArchive;
+
+// cached responses what are not used for this period are removed
+const CACHE_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+// the maximum 'limit' of the Hangar API
+const HANGAR_PAGE_SIZE: u32 = 25;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct AppData {
@@ -20,27 +34,207 @@ struct AppData {
     created_date: String,
     github_token: String,
     plugins: HashMap<String, PluginData>,
+    // key: plugin name, value: replaced versions (oldest first)
+    #[serde(default)]
+    history: HashMap<String, Vec<PluginData>>,
+    // the number of replaced versions (and their backed up jars) what are kept per plugin
+    #[serde(default = "default_max_history")]
+    max_history: usize,
+    #[serde(default)]
+    server: ServerConfig,
+    #[serde(default)]
+    github: GitHubConfig,
+    #[serde(default)]
+    modrinth: ModrinthConfig,
+    #[serde(default)]
+    hangar: HangarConfig,
+    #[serde(default)]
+    spiget: SpigetConfig,
+    // self-hosted GitLab / Gitea / Forgejo
+    #[serde(default)]
+    forges: Vec<ForgeHost>,
+    // key: plugin name (as written in 'depend'), value: what is given to 'register'
+    #[serde(default = "default_providers")]
+    providers: HashMap<String, String>,
+    // the number of threads what check releases and download jars at the same time
+    #[serde(default = "default_workers")]
+    workers: usize,
+    // '--offline' -> API responses are read from '.mngr/cache' only
+    #[serde(skip)]
+    offline: bool,
+    #[serde(skip)]
+    api: ApiClient,
+}
+
+#[derive(Debug, Default)]
+struct ApiClient {
+    // shared by all requests (keeps connections alive)
+    client: blocking::Client,
+    usage: Mutex<ApiUsage>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ApiUsage {
+    calls: u32,
+    not_modified: u32,
+    retries: u32,
+    // the last 'X-RateLimit-Remaining' and 'X-RateLimit-Reset' (unix time) of the GitHub API
+    remaining: Option<i64>,
+    reset: Option<i64>,
+}
+
+const MAX_RETRIES: u32 = 3;
+
+enum UpdateOutcome {
+    Skipped(String),
+    UpToDate(String),
+    // the new release, its download url and the verified jar (staged in 'plugins')
+    Downloaded(Box<PluginData>, String, PathBuf),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ForgeHost {
+    // e.g. 'https://git.example.com' or 'https://example.com/gitlab'
+    base_url: String,
+    #[serde(default)]
+    token: String,
+    // the number of release pages (up to 100 releases per page) what are fetched at most
+    #[serde(default = "default_max_pages")]
+    max_pages: u32,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ServerConfig {
+    // e.g. '1.20.4' (empty -> releases are not checked)
+    game_version: String,
+    // 'paper', 'spigot', 'purpur', 'folia' or 'velocity' (empty -> releases are not checked)
+    platform: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct GitHubConfig {
+    // GitHub Enterprise Server -> 'https://(host)/api/v3' and 'https://(host)'
+    api_url: String,
+    web_url: String,
+    // the number of release pages (100 releases per page) what are fetched at most
+    max_pages: u32,
+    // batch updates wait for the rate limit reset (or stop when it takes longer than 'max_wait' seconds)
+    // when the remaining calls are fewer than 'min_remaining'
+    min_remaining: i64,
+    max_wait: u64,
+}
+
+fn default_max_pages() -> u32 { 10 }
+
+fn default_min_remaining() -> i64 { 10 }
+
+fn default_max_wait() -> u64 { 60 }
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct ModrinthConfig {
+    api_url: String,
+    // e.g. ["paper", "spigot", "purpur"] (empty -> all loaders)
+    loaders: Vec<String>,
+    // e.g. ["1.20.4"] (empty -> 'game_version' of '[server]', or all game versions when it is empty too)
+    game_versions: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct HangarConfig {
+    api_url: String,
+    // 'PAPER', 'VELOCITY' or 'WATERFALL'
+    platform: String,
+    // the number of version pages (25 versions per page) what are fetched at most
+    max_pages: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(default)]
+struct SpigetConfig {
+    api_url: String,
+}
+
+impl Default for SpigetConfig {
+    fn default() -> Self {
+        SpigetConfig { api_url: String::from("https://api.spiget.org/v2") }
+    }
+}
+
+impl Default for HangarConfig {
+    fn default() -> Self {
+        HangarConfig {
+            api_url: String::from("https://hangar.papermc.io/api/v1"),
+            platform: String::from("PAPER"),
+            max_pages: default_max_pages(),
+        }
+    }
+}
+
+impl Default for GitHubConfig {
+    fn default() -> Self {
+        GitHubConfig {
+            api_url: String::from("https://api.github.com"),
+            web_url: String::from("https://github.com"),
+            max_pages: default_max_pages(),
+            min_remaining: default_min_remaining(),
+            max_wait: default_max_wait(),
+        }
+    }
+}
+
+impl Default for ModrinthConfig {
+    fn default() -> Self {
+        ModrinthConfig {
+            api_url: String::from("https://api.modrinth.com/v2"),
+            loaders: vec![String::from("paper"), String::from("spigot"), String::from("bukkit"), String::from("purpur")],
+            game_versions: Vec::new(),
+        }
+    }
+}
+
+fn default_workers() -> usize { 4 }
+
+fn default_max_history() -> usize { 5 }
+
+fn default_providers() -> HashMap<String, String> {
+    HashMap::from([
+        (String::from("Vault"), String::from("https://github.com/MilkBowl/Vault")),
+        (String::from("ProtocolLib"), String::from("https://github.com/dmulloy2/ProtocolLib")),
+        (String::from("LuckPerms"), String::from("modrinth:luckperms")),
+        (String::from("PlaceholderAPI"), String::from("hangar:HelpChat/PlaceholderAPI")),
+    ])
 }
 
 impl AppData {
     pub fn new(id: Option<String>, date: Option<String>, token: Option<String>) -> Self {
         AppData {
-            id: if id.is_some() { id.unwrap() } else { format!("{}", Uuid::new_v4()) },
-            created_date: if date.is_some() { date.unwrap() } else { Utc::now().to_string() },
-            github_token: if token.is_some() { token.unwrap() } else { String::new() },
+            id: id.unwrap_or_else(|| format!("{}", Uuid::new_v4())),
+            created_date: date.unwrap_or_else(|| Utc::now().to_string()),
+            github_token: token.unwrap_or_default(),
             plugins: HashMap::new(),
+            history: HashMap::new(),
+            server: ServerConfig::default(),
+            github: GitHubConfig::default(),
+            modrinth: ModrinthConfig::default(),
+            hangar: HangarConfig::default(),
+            spiget: SpigetConfig::default(),
+            forges: Vec::new(),
+            providers: default_providers(),
+            workers: default_workers(),
+            max_history: default_max_history(),
+            offline: false,
+            api: ApiClient::default(),
         }
     }
-
-    pub fn get_created_utc(&self) -> Option<DateTime<Utc>> {
-        let parse_result: ParseResult<DateTime<FixedOffset>> = DateTime::parse_from_rfc3339(self.created_date.as_str());
-        if parse_result.is_err() { return None };
-        Some(parse_result.unwrap().to_utc())
-    }
 }
 
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct PluginData {
     name: String,
     version: String,
@@ -49,34 +243,171 @@ struct PluginData {
     pre_release: bool,
     file_name: String,
     repository_url: String,
-    latest_in_the_time: bool
+    latest_in_the_time: bool,
+    #[serde(default)]
+    asset_id: u64,
+    #[serde(default)]
+    download_url: String,
+    #[serde(default)]
+    size: u64,
+    // GitHub asset digest of the jar (e.g. 'sha256:(hex)')
+    #[serde(default)]
+    digest: String,
+    // '.sha256' or '.sha512' asset that is published in the same release
+    #[serde(default)]
+    checksum_url: String,
+    // pinned by the user (e.g. 'sha256:(hex)'), it takes priority over the published ones
+    checksum: Option<String>,
+    // the release tag what the plugin is pinned to ('update --all' skips pinned plugins)
+    pinned: Option<String>,
+    // version requirement for updates (e.g. '^4.1', '~2.3.0', '<5')
+    constraint: Option<String>,
+    // glob (e.g. '*-paper-*.jar') or 'regex:(pattern)' to select a release asset by its name
+    asset_pattern: Option<String>,
+    #[serde(default)]
+    source: SourceKind,
+    // id of the project on the source (e.g. Modrinth project id)
+    #[serde(default)]
+    project_id: String,
+    // response headers of the 'url' source, to detect changes
+    #[serde(default)]
+    etag: String,
+    #[serde(default)]
+    last_modified: String,
+    // read from 'plugin.yml' (or others) of the installed jar
+    metadata: Option<PluginMetadata>,
+    // installable assets of the release (only while resolving, not saved)
+    #[serde(skip)]
+    candidates: Vec<ReleaseAsset>,
+    // game versions (or ranges like '1.20-1.20.4') and platforms what the release supports (only while resolving, not saved)
+    #[serde(skip)]
+    game_versions: Vec<String>,
+    #[serde(skip)]
+    platforms: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct PluginMetadata {
+    // 'plugin.yml', 'paper-plugin.yml', 'bungee.yml' or 'velocity-plugin.json'
+    descriptor: String,
+    name: String,
+    version: String,
+    main: String,
+    #[serde(default)]
+    api_version: Option<String>,
+    #[serde(default)]
+    authors: Vec<String>,
+    #[serde(default)]
+    depend: Vec<String>,
+    #[serde(default)]
+    softdepend: Vec<String>,
+    #[serde(default)]
+    loadbefore: Vec<String>,
+    #[serde(default)]
+    provides: Vec<String>,
+    #[serde(default)]
+    folia_supported: bool,
+}
+
+// key: the date of the release
+type Releases = HashMap<DateTime<Utc>, PluginData>;
+
+// a result and the messages of the task what made it (see 'parallel_map')
+type Captured<U> = (U, Vec<String>);
+
+// where the messages of the release checks and downloads go
+struct Messages {
+    // Some -> kept for the main thread instead of printed. (worker threads would interleave them)
+    kept: Option<RefCell<Vec<String>>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum SourceKind {
+    #[default]
+    GitHub,
+    Modrinth,
+    Hangar,
+    Spigot,
+    Jenkins,
+    Url,
+    File,
+    GitLab,
+    Gitea,
+    Actions,
+}
+
+#[derive(Debug, Clone, Default)]
+struct ReleaseAsset {
+    id: u64,
+    name: String,
+    download_url: String,
+    size: u64,
+    digest: String,
+    checksum_url: String,
+    created_at: String,
+}
+
+impl Messages {
+    fn printed() -> Messages {
+        Messages { kept: None }
+    }
+
+    fn kept() -> Messages {
+        Messages { kept: Some(RefCell::new(Vec::new())) }
+    }
+
+    fn line(&self, line: String) {
+        match &self.kept {
+            Some(lines) => lines.borrow_mut().push(line),
+            None => println!("{}", line),
+        }
+    }
+
+    fn into_lines(self) -> Vec<String> {
+        self.kept.map(RefCell::into_inner).unwrap_or_default()
+    }
 }
 
 impl PluginData {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(name: String, version: String, date: DateTime<Utc>,description: Option<Vec<String>>, pre_release: bool, file_name: String, repository_url: String, is_latest: bool) -> Self {
         PluginData {
             name,
             version,
             introduced_date: date.to_string(),
-            description: if description.is_some() { description } else { None },
+            description,
             pre_release,
             file_name,
             repository_url,
             latest_in_the_time: is_latest,
+            asset_id: 0,
+            download_url: String::new(),
+            size: 0,
+            digest: String::new(),
+            checksum_url: String::new(),
+            checksum: None,
+            pinned: None,
+            constraint: None,
+            asset_pattern: None,
+            source: SourceKind::GitHub,
+            project_id: String::new(),
+            etag: String::new(),
+            last_modified: String::new(),
+            metadata: None,
+            candidates: Vec::new(),
+            game_versions: Vec::new(),
+            platforms: Vec::new(),
         }
     }
 
-    pub fn empty_new() -> Self {
-        PluginData {
-            name: String::new(),
-            version: String::new(),
-            introduced_date: String::new(),
-            description: None,
-            pre_release: false,
-            file_name: String::new(),
-            repository_url: String::new(),
-            latest_in_the_time: true,
-        }
+    pub fn apply_asset(&mut self, asset: &ReleaseAsset) {
+        self.file_name = String::from(&asset.name);
+        self.asset_id = asset.id;
+        self.download_url = String::from(&asset.download_url);
+        self.size = asset.size;
+        self.digest = String::from(&asset.digest);
+        self.checksum_url = String::from(&asset.checksum_url);
     }
 
     pub fn content(&self) -> String {
@@ -84,20 +415,73 @@ impl PluginData {
         content.push('\n');
         content.push_str(format!("- name: {}\n", self.name).as_str());
         content.push_str(format!("- version: {}\n", self.version).as_str());
-        content.push_str(format!("- introduced date: {}\n", self.introduced_date.to_string()).as_str());
-        content.push_str(format!("- pre release: {}\n", self.pre_release.to_string()).as_str());
+        content.push_str(format!("- introduced date: {}\n", self.introduced_date).as_str());
+        content.push_str(format!("- pre release: {}\n", self.pre_release).as_str());
         content.push_str(format!("- filename: {}\n", self.file_name.as_str()).as_str());
         content.push_str(format!("- repository url: {}", self.repository_url.as_str()).as_str());
+        if self.source != SourceKind::GitHub { content.push_str(format!("\n- source: {:?}", self.source).as_str()); }
+        if let Some(pinned) = &self.pinned { content.push_str(format!("\n- pinned: {}", pinned).as_str()); }
+        if let Some(constraint) = &self.constraint { content.push_str(format!("\n- constraint: {}", constraint).as_str()); }
+        if let Some(pattern) = &self.asset_pattern { content.push_str(format!("\n- asset pattern: {}", pattern).as_str()); }
+        if let Some(metadata) = &self.metadata {
+            content.push_str(format!("\n- plugin: {} {} ({})", metadata.name, metadata.version, metadata.descriptor).as_str());
+            content.push_str(format!("\n- main: {}", metadata.main).as_str());
+            if let Some(api_version) = &metadata.api_version { content.push_str(format!("\n- api version: {}", api_version).as_str()); }
+            if !metadata.authors.is_empty() { content.push_str(format!("\n- authors: {}", metadata.authors.join(", ")).as_str()); }
+            if !metadata.depend.is_empty() { content.push_str(format!("\n- depend: {}", metadata.depend.join(", ")).as_str()); }
+            if !metadata.softdepend.is_empty() { content.push_str(format!("\n- softdepend: {}", metadata.softdepend.join(", ")).as_str()); }
+        }
         content.push('\n');
         content
     }
 }
 
-fn main() {
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CachedResponse {
+    url: String,
+    #[serde(default)]
+    etag: String,
+    #[serde(default)]
+    last_modified: String,
+    // 'Link' header (pagination)
+    #[serde(default)]
+    link: String,
+    // 'X-Next-Page' header (GitLab pagination)
+    #[serde(default)]
+    next_page: String,
+    body: String,
+    #[serde(skip)]
+    status: u16,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LockFile {
+    // key: plugin name
+    plugins: BTreeMap<String, LockedPlugin>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct LockedPlugin {
+    name: String,
+    tag: String,
+    asset_id: u64,
+    file_name: String,
+    repository_url: String,
+    download_url: String,
+    size: u64,
+    sha256: String,
+    // so a plugin what is not in mngr.toml is restored with its source
+    #[serde(default)]
+    source: SourceKind,
+    #[serde(default)]
+    project_id: String,
+}
+
+fn main() -> ExitCode {
     let config_path: Option<PathBuf> = get_config_path();
     if config_path.is_none() {
         println!("{}", "Failed to get path.".red());
-        return;
+        return ExitCode::FAILURE;
     }
     let app: Option<AppData> =
         if !config_path.unwrap().exists() {
@@ -108,10 +492,26 @@ fn main() {
 
     if app.is_none() {
         println!("{}", "Failed to create 'mngr.toml'. Process closed.".red());
-        return;
+        return ExitCode::FAILURE;
     }
 
     let mut app: AppData = app.unwrap();
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    // '--offline' is accepted only before the command (e.g. 'mngr --offline outdated')
+    if args.first().is_some_and(|a| a == "--offline") {
+        args.remove(0);
+        app.offline = true;
+    }
+    if !args.is_empty() {
+        // non-interactive mode (e.g. 'mngr update --all' from cron or CI)
+        let code: ExitCode = run_subcommand(&mut app, &args);
+        if changes_jars(&args[0]) && refresh_metadata(&mut app) { resolve_dependencies(&mut app); }
+        prune_cache(&app);
+        report_api_usage(&app);
+        config_update(&app);
+        return code;
+    }
+
     print!("mngr > ");
     stdout().flush().unwrap();
     loop {
@@ -125,16 +525,167 @@ fn main() {
             "register" | "R" | "r" => register_listener(&mut app),
             "unregister" | "UR" | "ur" => unregister_listener(&mut app),
             "list" | "L" | "l" => print_plugins(&app),
+            "sync" | "S" | "s" => sync_listener(&mut app),
+            "install" | "I" | "i" => { install(&mut app); },
+            "rollback" | "RB" | "rb" => rollback_listener(&mut app),
             "update" | "U" | "u" => update_listener(&mut app),
-            "remaining" | "rate" => rate_limit_command(&app),
+            "deps" | "D" | "d" => {
+                refresh_metadata(&mut app);
+                resolve_dependencies(&mut app);
+            },
+            "outdated" | "O" | "o" => { outdated(&app); },
+            "remaining" | "rate" => { rate_limit_command(&app); },
             _ => {
                 println!("{}", "Enter 'help' or 'H', displayed command helps.".underline());
             },
         }
+        if changes_jars(input) && refresh_metadata(&mut app) { resolve_dependencies(&mut app); }
+        report_api_usage(&app);
         print!("mngr > ");
         stdout().flush().unwrap();
     }
+    prune_cache(&app);
     config_update(&app);
+    ExitCode::SUCCESS
+}
+
+fn changes_jars(command: &str) -> bool {
+    // the metadata of the jars is read again only after the commands what place jars
+    matches!(command, "register" | "R" | "r" | "install" | "I" | "i" | "update" | "U" | "u" | "sync" | "S" | "s" | "rollback" | "RB" | "rb")
+}
+
+fn run_subcommand(app: &mut AppData, args: &[String]) -> ExitCode {
+    // exit codes: 0 -> succeeded, 1 -> failed, 2 -> invalid arguments
+    let usage: ExitCode = ExitCode::from(2);
+    let succeeded = |result: bool| if result { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+    match args[0].as_str() {
+        "help" | "-h" | "--help" => {
+            show_usage();
+            ExitCode::SUCCESS
+        },
+        "register" => {
+            if args.len() != 2 {
+                println!("{}", "Usage: mngr register [((repository url) | modrinth:(project) | hangar:(owner)/(project) | spigot:(resource id) | jenkins:(job url) | gitlab:(project url) | gitea:(repository url) | actions:(repository url)#(branch)/(artifact))[@(tag)] | url:(link) | file:(path)]".red());
+                return usage;
+            }
+            succeeded(register(app, &args[1]))
+        },
+        "unregister" => {
+            match args.len() {
+                2 => succeeded(unregister(app, "-n", &args[1])),
+                3 if args[1] == "-n" || args[1] == "-f" => succeeded(unregister(app, &args[1], &args[2])),
+                _ => {
+                    println!("{}", "Usage: mngr unregister [-n (plugin name) | -f (plugin file's name) | (plugin name)]".red());
+                    usage
+                }
+            }
+        },
+        "update" => {
+            if args.len() == 4 && args[2] == "--to" {
+                if !app.plugins.contains_key(&args[1]) {
+                    println!("{} {}", "Not registered:".red(), &args[1]);
+                    return ExitCode::FAILURE;
+                }
+                return succeeded(update_to(app, &args[1], &args[3]));
+            }
+            if args.len() == 3 && args[2] == "--unpin" {
+                if !app.plugins.contains_key(&args[1]) {
+                    println!("{} {}", "Not registered:".red(), &args[1]);
+                    return ExitCode::FAILURE;
+                }
+                app.plugins.get_mut(&args[1]).unwrap().pinned = None;
+                return succeeded(all_update(&args[1..2], app));
+            }
+            let targets: Vec<String> = match args.get(1).map(|a| a.as_str()) {
+                Some("--all") if args.len() == 2 => get_plugin_names(app),
+                Some("--stable") if args.len() == 2 => get_not_prerelease_plugins_name(app).unwrap_or_default(),
+                Some(_) if !args[1].starts_with("--") => args[1..].to_vec(),
+                _ => {
+                    println!("{}", "Usage: mngr update [--all | --stable | (plugin name) ... | (plugin name) --to (tag) | (plugin name) --unpin]".red());
+                    return usage;
+                }
+            };
+            let unknown: Vec<&String> = targets.iter().filter(|t| !app.plugins.contains_key(*t)).collect();
+            if !unknown.is_empty() {
+                for name in unknown {
+                    println!("{} {}", "Not registered:".red(), name);
+                }
+                return ExitCode::FAILURE;
+            }
+            // pinned plugins what are named explicitly are not updated silently
+            let pinned: Vec<String> = targets.iter()
+                .filter(|t| !args[1].starts_with("--") && app.plugins.get(*t).unwrap().pinned.is_some())
+                .map(String::from)
+                .collect();
+            let updated: bool = all_update(&targets, app);
+            for name in &pinned {
+                println!("{} {} {}", "Pinned:".red(), name, format!("(run 'mngr update {} --unpin' to update it)", name).yellow());
+            }
+            succeeded(updated && pinned.is_empty())
+        },
+        "install" => {
+            match args.get(1).map(|a| a.as_str()) {
+                None => succeeded(install(app)),
+                Some("--locked") if args.len() == 2 => succeeded(install_locked(app)),
+                _ => {
+                    println!("{}", "Usage: mngr install [--locked]".red());
+                    usage
+                }
+            }
+        },
+        "rollback" => {
+            if args.len() != 2 && args.len() != 3 {
+                println!("{}", "Usage: mngr rollback (plugin name) [version]".red());
+                return usage;
+            }
+            succeeded(rollback(app, &args[1], args.get(2).map(|v| v.as_str())))
+        },
+        "constraint" => {
+            match args.len() {
+                3 if args[2] == "--clear" => succeeded(set_constraint(app, &args[1], None)),
+                3 => succeeded(set_constraint(app, &args[1], Some(&args[2]))),
+                _ => {
+                    println!("{}", "Usage: mngr constraint (plugin name) [(constraint) | --clear]".red());
+                    usage
+                }
+            }
+        },
+        "asset" => {
+            match args.len() {
+                3 if args[2] == "--clear" => succeeded(set_asset_pattern(app, &args[1], None)),
+                3 => succeeded(set_asset_pattern(app, &args[1], Some(&args[2]))),
+                _ => {
+                    println!("{}", "Usage: mngr asset (plugin name) [(pattern) | --clear]".red());
+                    usage
+                }
+            }
+        },
+        "list" => {
+            print_plugins(app);
+            ExitCode::SUCCESS
+        },
+        "sync" => {
+            let options: &[String] = &args[1..];
+            if options.iter().any(|o| o != "--download" && o != "--remove-orphans") {
+                println!("{}", "Usage: mngr sync [--download] [--remove-orphans]".red());
+                return usage;
+            }
+            let download: bool = options.iter().any(|o| o == "--download");
+            let remove_orphans: bool = options.iter().any(|o| o == "--remove-orphans");
+            succeeded(sync_command(app, download, remove_orphans))
+        },
+        "deps" => {
+            refresh_metadata(app);
+            succeeded(resolve_dependencies(app))
+        },
+        "outdated" => succeeded(outdated(app)),
+        "rate" | "remaining" => succeeded(rate_limit_command(app)),
+        _ => {
+            println!("{} '{}'", "Unknown command.".red(), &args[0]);
+            show_usage();
+            usage
+        }
+    }
 }
 
 fn config_update(app: &AppData) {
@@ -145,7 +696,7 @@ fn config_update(app: &AppData) {
         return;
     }
     let path: PathBuf = path.unwrap();
-    let mut file: Result<File> = File::create(path.as_path());
+    let file: Result<File> = File::create(path.as_path());
     if file.is_err() {
         println!("{}", "Failed to handle 'mngr.toml'.".red());
         println!("{}", "mngr will not save the modified data.".yellow());
@@ -166,9 +717,9 @@ fn unregister_listener(app: &mut AppData) {
         let mut input: String = String::new();
         stdin().read_line(&mut input).ok();
         let input: String = input.trim_end().to_string();
-        let mut args: Vec<String> = input.split(" ").map(|c| String::from(c)).collect();
+        let mut args: Vec<String> = input.split(' ').map(String::from).collect();
         // plugin_name or file_name -> '-n (plugin name)' or '-f (plugin file's name)', and default '(plugin name)' works like '-n (plugin name)'
-        if args.len() < 1 || args.len() > 2 || args[0].is_empty() {
+        if args.is_empty() || args.len() > 2 || args[0].is_empty() {
             println!("{}", "Invalid arguments. It needs only 1 or 2 arguments.".red());
             println!("{}", "'-n (plugin name)', '-f (plugin file's name)' or '(plugin name)'.".yellow());
             println!("{}", &help_1);
@@ -187,34 +738,144 @@ fn unregister_listener(app: &mut AppData) {
             }
         }
         if args.len() == 1 { args.insert(0, String::from("-n")) };
-        let removed: Option<String>;
-        if args.get(0).unwrap().as_str() == "-n" {
-            let result: Option<PluginData> = app.plugins.remove(&args[1]);
-            removed = if result.is_some() { Some(result.unwrap().file_name) } else { None };
-        } else if args.get(0).unwrap().as_str() == "-f" {
-            let size: usize = app.plugins.len();
-            app.plugins.retain(|k, v| v.file_name != args[1]);
-            removed = if app.plugins.len() != size { Some(String::from(&args[1])) } else { None };
-        } else {
+        if args[0].as_str() != "-n" && args[0].as_str() != "-f" {
             println!("{}", &help_1);
             println!("{}", &help_2);
             continue 'main;
         }
+        unregister(app, &args[0], &args[1]);
+    }
+}
 
-        if removed.is_none() {
-            println!("{}{} {}{}", "Failed to unregister. (".red(), if args[0].as_str() == "-n" { "FileName:" } else { "PluginName:" }, &args[1], ")".red());
-            continue
+fn unregister(app: &mut AppData, flag: &str, target: &str) -> bool {
+    // flag: '-n' -> target is a plugin name, '-f' -> target is a plugin file's name
+    let keys: Vec<String> = match flag {
+        "-n" => app.plugins.contains_key(target).then(|| String::from(target)).into_iter().collect(),
+        "-f" => app.plugins.iter().filter(|(_, v)| v.file_name == target).map(|(k, _)| String::from(k)).collect(),
+        _ => Vec::new(),
+    };
+    if keys.is_empty() {
+        println!("{}{} {}{}", "Failed to unregister. (".red(), if flag == "-n" { "PluginName:" } else { "FileName:" }, target, ")".red());
+        return false
+    }
+    let removed: String = String::from(&app.plugins.get(&keys[0]).unwrap().file_name);
+    if !forget_plugins(app, &keys) { return false };
+    if !delete_plugin_jar(&removed, true) { return false };
+    println!("{}", "The plugin has been successfully unregistered.".green());
+    println!("{} {}", "Removed:".green(), &removed);
+    true
+}
+
+fn forget_plugins(app: &mut AppData, keys: &[String]) -> bool {
+    // removes the entries from mngr.toml and mngr.lock (the jars are left as they are)
+    // refused when other plugins hard-depend on one of them
+    for key in keys {
+        let dependents: Vec<String> = get_dependents(app, key);
+        if !dependents.is_empty() {
+            println!("{} {} {} {}", "Refused to unregister".red(), key, "- it is required by".red(), dependents.join(", "));
+            return false
+        }
+    }
+    for key in keys {
+        if let Some(removed) = app.plugins.remove(key) { unlock_plugin(&removed.file_name); }
+    }
+    true
+}
+
+fn get_dependents(app: &AppData, name: &str) -> Vec<String> {
+    // plugins what hard-depend on the given one (and no other installed jar provides it)
+    let metadata: Option<&PluginMetadata> = app.plugins.get(name).and_then(|pl| pl.metadata.as_ref());
+    if metadata.is_none() { return Vec::new() };
+    let metadata: &PluginMetadata = metadata.unwrap();
+    let provided: Vec<String> = get_provided_names(app, Some(name));
+    let lost: Vec<String> = std::iter::once(&metadata.name).chain(metadata.provides.iter())
+        .map(|n| n.to_lowercase())
+        .filter(|n| !provided.contains(n))
+        .collect();
+    let mut dependents: Vec<String> = app.plugins.iter()
+        .filter(|(key, _)| key.as_str() != name)
+        .filter_map(|(_, pl)| pl.metadata.as_ref())
+        .filter(|m| m.depend.iter().any(|d| lost.contains(&d.to_lowercase())))
+        .map(|m| String::from(&m.name))
+        .collect();
+    dependents.sort();
+    dependents
+}
+
+fn get_provided_names(app: &AppData, except: Option<&str>) -> Vec<String> {
+    // names (and 'provides') of the installed plugins in lower case. 'except' -> a registered plugin what is ignored
+    let mut names: Vec<String> = Vec::new();
+    for (key, plugin) in &app.plugins {
+        if Some(key.as_str()) == except { continue };
+        if let Some(metadata) = &plugin.metadata {
+            names.push(metadata.name.to_lowercase());
+            names.extend(metadata.provides.iter().map(|p| p.to_lowercase()));
+        }
+    }
+    // jars what are placed without mngr
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    for entry in directory.and_then(|d| fs::read_dir(d).ok()).into_iter().flatten().flatten() {
+        let file_name: String = entry.file_name().to_string_lossy().to_string();
+        if !file_name.ends_with(".jar") || app.plugins.values().any(|pl| pl.file_name == file_name) { continue };
+        if let Some(metadata) = read_metadata(&entry.path()) {
+            names.push(metadata.name.to_lowercase());
+            names.extend(metadata.provides.iter().map(|p| p.to_lowercase()));
         }
-        let removed: String = removed.unwrap();
+    }
+    names
+}
 
-        if delete_plugin_jar(&removed, true) {
-            println!("{}", "The plugin has been successfully unregistered.".green());
-            println!("{} {}", "Removed:".green(), &removed);
-        } else { continue }
+fn resolve_dependencies(app: &mut AppData) -> bool {
+    // warns about missing hard dependencies ('depend'), and offers to register the known providers.
+    let mut checked: Vec<String> = Vec::new();
+    loop {
+        let provided: Vec<String> = get_provided_names(app, None);
+        // key: dependency in lower case, value: (dependency, required by)
+        let mut missing: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+        for metadata in app.plugins.values().filter_map(|pl| pl.metadata.as_ref()) {
+            for dependency in &metadata.depend {
+                if provided.contains(&dependency.to_lowercase()) { continue };
+                missing.entry(dependency.to_lowercase())
+                    .or_insert_with(|| (String::from(dependency), Vec::new()))
+                    .1.push(String::from(&metadata.name));
+            }
+        }
+        let mut installed: bool = false;
+        for (key, (dependency, required_by)) in &mut missing {
+            if checked.contains(key) { continue };
+            checked.push(String::from(key.as_str()));
+            required_by.sort();
+            println!("{} {} {} {}", "Missing dependency:".yellow(), dependency, "<- required by".yellow(), required_by.join(", "));
+            let provider: Option<String> = app.providers.iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(dependency))
+                .map(|(_, target)| String::from(target));
+            if provider.is_none() {
+                println!("{} '{}' {}", "Register it by yourself, or write where".yellow(), dependency, "is published in '[providers]' of 'mngr.toml'.".yellow());
+                continue
+            }
+            let provider: String = provider.unwrap();
+            if !stdin().is_terminal() {
+                println!("{} 'mngr register {}'", "It can be registered with".yellow(), &provider);
+                continue
+            }
+            let answer: String = prompt_input(format!("Register '{}' from '{}'? (y/N) > ", dependency, &provider).as_str());
+            if !answer.eq_ignore_ascii_case("y") { continue };
+            let before: Vec<String> = app.plugins.keys().cloned().collect();
+            if !register(app, &provider) { continue };
+            let name: Option<String> = app.plugins.keys().find(|k| !before.contains(k)).cloned();
+            if name.is_none() { continue };
+            let plugin: &PluginData = app.plugins.get(&name.unwrap()).unwrap();
+            // 'file:' places the jar when it is registered
+            let placed: bool = get_plugins_directory_path().is_some_and(|d| d.join(&plugin.file_name).is_file());
+            installed |= placed || jar_download(plugin, None, app);
+        }
+        if !installed { return missing.is_empty() };
+        // the dependencies of the new plugins are checked too
+        refresh_metadata(app);
     }
 }
 
-fn delete_plugin_jar(filename: &String, is_unregister: bool) -> bool {
+fn delete_plugin_jar(filename: &str, is_unregister: bool) -> bool {
     let plugins_directory: Option<PathBuf> = get_plugins_directory_path();
     if plugins_directory.is_none() {
         println!("{}", "Failed to get 'plugins' directory's path.".red());
@@ -222,7 +883,7 @@ fn delete_plugin_jar(filename: &String, is_unregister: bool) -> bool {
         return false
     }
     let directory: PathBuf = plugins_directory.unwrap();
-    let mut file_path: PathBuf = PathBuf::from(directory);
+    let mut file_path: PathBuf = directory;
     file_path.push(filename);
     if fs::remove_file(file_path.to_str().unwrap()).is_err() {
         println!("{} -> {}", "Failed to delete the file.".red(), file_path.to_str().unwrap());
@@ -241,7 +902,7 @@ fn register_listener(app: &mut AppData) {
         let mut input: String = String::new();
         stdin().read_line(&mut input).ok();
         let input: String = input.trim_end().to_string();
-        let args: Vec<String> = input.split(" ").map(|c| String::from(c)).collect();
+        let args: Vec<String> = input.split(' ').map(String::from).collect();
         if args.len() != 1 {
             println!("{}{}", "Failed to parse arguments. It needs only 1 arg. -> ".red(), "'GitHub repository URL'".yellow());
         } else if args.len() == 1 {
@@ -273,64 +934,73 @@ fn register_listener(app: &mut AppData) {
     }
 }
 
-fn register(app: &mut AppData, url: &String) -> bool {
-    // https://docs.rs/reqwest/latest/reqwest/
-    // (API URL) https://api.github.com/repos/(UserName)/(RepositoryName)/releases
-    // (NORMAL URL) https://github.com/(UserName)/(RepositoryName) or .git
-    let url_pattern: &str = r"^https://github.com/(?=.{0,39}$)(?!.*--)[a-zA-Z0-9]([a-zA-Z0-9-]*[a-zA-Z0-9])?/[\w\.-]+$";
-    let url_pattern: Regex = Regex::new(url_pattern).unwrap();
-     if !url_pattern.is_match(url.as_str()).unwrap() {
-        println!("{}", "Failed to parse the given url.".red());
-        return false
+fn register(app: &mut AppData, url: &str) -> bool {
+    // (GitHub) https://github.com/(UserName)/(RepositoryName)
+    // (Modrinth) modrinth:(project slug or id)
+    // (Hangar) hangar:(owner)/(project slug)
+    // (SpigotMC) spigot:(resource id)
+    // (Jenkins) jenkins:(job url) -> successful builds (the build number is the version)
+    // (GitLab) gitlab:(project url)
+    // (Gitea / Forgejo) gitea:(repository url) or forgejo:(repository url)
+    // (GitHub Actions) actions:(repository url)#(branch)/(artifact name) -> successful runs (the run id is the version)
+    // (Direct URL) url:(link to a jar) -> updated when its ETag or Last-Modified is changed
+    // (Local file) file:(path to a jar) -> tracked, but never updated automatically
+    // '@(tag)' at the end pins the plugin to the release.
+    if let Some(path) = url.strip_prefix("file:") {
+        return register_file(app, path)
     }
-    let mut parsed: Vec<String> = Vec::new();
-    url.split("/").for_each(|c| parsed.push(String::from(c)));
-    let author: String = String::from(&parsed[3]);
-    let repository_name: String =
-        if parsed[4].ends_with(".git") { parsed[4].replace(".git", "") }
-        else { String::from(&parsed[4]) };
-    let url: String = format!("https://api.github.com/repos/{}/{}/releases", &author, repository_name);
-
-    let client: blocking::Client = blocking::Client::new();
-    let mut builder: RequestBuilder = client.get(&url);
-    if !&app.github_token.is_empty() {
-        builder = builder.header("Authorization", format!("token {}", &app.github_token));
+    if let Some(link) = url.strip_prefix("url:") {
+        return register_url(app, link)
     }
-    builder = builder.header("X-GitHub-Api-Version", "2022-11-28");
-    builder = builder.header("User-Agent", "mngr");
-    builder = builder.header("Accept", "application/vnd.github.v3+json");
-    builder = builder.header("Content-Type", "application/json");
-
-    let response: reqwest::Result<Response> = builder.send();
+    let (target, tag): (&str, Option<&str>) = match url.rsplit_once('@') {
+        Some((target, tag)) => (target, Some(tag)),
+        None => (url, None),
+    };
+    let out: Messages = Messages::printed();
+    let fetched: Option<Releases> =
+        if let Some(project) = target.strip_prefix("modrinth:") {
+            fetch_modrinth_releases(project, None, app, &out)
+        } else if let Some(project) = target.strip_prefix("hangar:") {
+            fetch_hangar_releases(project, None, app, &out)
+        } else if let Some(resource) = target.strip_prefix("spigot:") {
+            fetch_spiget_releases(resource, app, &out)
+        } else if let Some(job) = target.strip_prefix("jenkins:") {
+            fetch_jenkins_releases(job, None, app, &out)
+        } else if let Some(project) = target.strip_prefix("gitlab:") {
+            fetch_gitlab_releases(project, None, app, &out)
+        } else if let Some(repository) = target.strip_prefix("gitea:").or_else(|| target.strip_prefix("forgejo:")) {
+            fetch_gitea_releases(repository, None, app, &out)
+        } else if let Some(artifact) = target.strip_prefix("actions:") {
+            fetch_actions_releases(artifact, app, &out)
+        } else {
+            fetch_github_releases_for_register(target, tag, app)
+        };
+    if fetched.is_none() { return false };
+    let mut releases: Releases = fetched.unwrap();
+    if tag.is_none() { remove_incompatible(&mut releases, app, &out) };
 
-    if response.is_err() {
-        println!("{}", "Failed to send a request or receive a response.".yellow());
+    let response_result: Option<PluginData> = match tag {
+        Some(tag) => releases.into_values().find(|pl| pl.version == tag),
+        None => get_latest_plugin(&mut releases),
+    };
+    if response_result.is_none() {
+        println!("Failed to get plugin data.");
+        if let Some(tag) = tag { println!("{} '{}'", "No release has the tag".yellow(), tag); }
         return false
     }
-    let response: Response = response.unwrap();
-
-    match &response.status().as_u16() {
-        200 => (),
-        _ => {
-            println!("{} Code: {}", "I received a not correct status code.".yellow(), &response.status().as_u16());
-            println!("{}", "Check the destination of the url.".yellow());
-            if &response.status().as_u16() == &401 {
-                println!("{}{}", "\n", "Detected 401 error.".yellow());
-                println!("{}", "This error means that you sent an incorrect authorization token with the request.".yellow().underline());
-                println!("{}", "You have to check your github api token what written in 'mngr.toml' and those expiration.".yellow().underline());
-            }
-            return false;
-        }
-    }
-    let api_remaining: Option<i16> = get_rate_limit_remaining(&response);
-    let response_result: Option<PluginData> = get_latest_plugin(&mut response_parser(response));
-    if response_result.is_none() {
-        println!("{}", "Failed to get plugin data.");
+    let mut plugin: PluginData = response_result.unwrap();
+    if plugin.source == SourceKind::Spigot && plugin.download_url.is_empty() {
+        println!("{} {}", "Only the latest version of a Spigot resource can be installed:".red(), &plugin.version);
         return false
     }
-    let plugin: PluginData = response_result.unwrap();
+    if let Some(reason) = get_incompatibility(&plugin, &app.server) {
+        println!("{} {} ({})", "The release may not work on this server:".yellow(), &plugin.version, reason);
+    }
+    plugin.pinned = tag.map(String::from);
+    pick_asset(&mut plugin);
     let name: String = String::from(&plugin.name);
     let plugin_info: String = plugin.content();
+    let source: SourceKind = plugin.source;
     if app.plugins.contains_key(&name) {
         println!("{}", "The plugin has already registered.".yellow());
         let registered: &PluginData = app.plugins.get(&name).unwrap();
@@ -339,123 +1009,1304 @@ fn register(app: &mut AppData, url: &String) -> bool {
     }
     app.plugins.insert(name, plugin);
 
-    let api_remaining: String =
-        if api_remaining.is_some() { api_remaining.unwrap().to_string() }
-        else { String::from("UNKNOWN") };
     println!("{}", "The plugin has been successfully registered.".green());
     println!("{}", plugin_info);
-    println!("API CALL REMAINING: {}", api_remaining);
+    if source == SourceKind::GitHub {
+        let api_remaining: String = match app.api.usage.lock().unwrap().remaining {
+            Some(remaining) => remaining.to_string(),
+            None => String::from("UNKNOWN"),
+        };
+        println!("API CALL REMAINING: {}", api_remaining);
+    }
     true
 }
 
-fn get_plugins_directory_path() -> Option<PathBuf> {
-    let mut current: PathBuf = match env::current_dir() {
-        Ok(path) => path,
-        _ => return None,
-    };
-    current.push("plugins");
-    return Some(current)
+fn fetch_github_releases_for_register(url: &str, tag: Option<&str>, app: &AppData) -> Option<Releases> {
+    // https://docs.rs/reqwest/latest/reqwest/
+    // (API URL) (api_url)/repos/(UserName)/(RepositoryName)/releases
+    // (NORMAL URL) (web_url)/(UserName)/(RepositoryName) or .git
+    let web_url: &str = app.github.web_url.trim_end_matches('/');
+    let url_pattern: String = format!(r"^{}/(?=.{{0,39}}$)(?!.*--)[a-zA-Z0-9]([a-zA-Z0-9-]*[a-zA-Z0-9])?/[\w\.-]+$", fancy_regex::escape(web_url));
+    let url_pattern: Regex = Regex::new(&url_pattern).unwrap();
+    if !url_pattern.is_match(url).unwrap() {
+        println!("{}", "Failed to parse the given url.".red());
+        return None
+    }
+    let url: String = get_releases_url(url, app).unwrap();
+    let out: Messages = Messages::printed();
+    let fetched: Releases = fetch_github_releases(&get_single_release_url(&url, tag), None, true, app, &out)?;
+    // no stable release (or it has no jar) -> the latest one in the list
+    if fetched.is_empty() && tag.is_none() { return fetch_github_releases(&url, None, false, app, &out) };
+    Some(fetched)
 }
 
-
-fn update_listener(app: &mut AppData) {
-    // input types
-    // (implemented) #all -> all update to latest version
-    // (implemented) #!pre -> update to latest "stable" release
-    // (no used) #only-marked -> updates what is marked 'latest'
-    // (no used) #!only-marked -> updates what is not marked 'latest'
-    // #pls (plugin_name,plugin_name,plugin_name) -> updates what is specified
-    // #~(RFC3339 formatted date) -> updates what is published before specified date
-    // #(RFC3339 formatted date)~ -> updates what is published after specified date
-    // #plv -> Enter select plugin and version mode.
-    //
-    loop {
-        print!("mngr > update > ");
-        stdout().flush().unwrap();
-        let mut input: String = String::new();
-        stdin().read_line(&mut input).ok();
-        let input: String = input.trim_end().to_string();
-        match input.as_str() {
-            "exit" | "E" | "e" => break,
-            "#all" => {
-                let all: Vec<String> = app.plugins.keys().map(|k: &String| String::from(k)).collect();
-                all_update(&all, app);
-            }
-            "#!pre" => {
-                let without_pre: Option<Vec<String>> = get_not_prerelease_plugins_name(&app);
-                if without_pre.is_none() {
-                    println!("{}", "mngr does not have any plugins that are marked as 'pre-release'.".green());
-                    continue
-                }
-                all_update(&without_pre.unwrap(), app);
-            },
-            "#multi" => {
-                multiple_plugins_update_listener(app);
-            },
-            "" => {
-                println!("'{}' = {}", "#all".green(), "To update all plugins that are registered.");
-                println!("'{}' = {}", "#!pre".green(), "To update that are not marked 'pre-release'.");
-                println!("'{}' = {}", "#multi".green(), "To update that are specified plugins.");
-            },
-            _ => (),
-        }
+fn get_single_release_url(releases_url: &str, tag: Option<&str>) -> String {
+    // (releases url)/tags/(tag) or (releases url)/latest
+    match tag {
+        Some(tag) => format!("{}/tags/{}", releases_url, percent_encode(tag)),
+        None => format!("{}/latest", releases_url),
     }
 }
 
-fn get_not_prerelease_plugins_name(app: &AppData) -> Option<Vec<String>> {
-    if app.plugins.is_empty() { return None };
-    let mut result:  Vec<String> = Vec::new();
-    for pl in app.plugins.values() {
-        if !pl.pre_release { result.push(String::from(&pl.name)); }
+fn percent_encode(text: &str) -> String {
+    // everything except the unreserved characters of RFC 3986 (e.g. '/' and '+' in tags)
+    let mut encoded: String = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~".contains(&byte) { encoded.push(byte as char); }
+        else { encoded.push_str(&format!("%{:02X}", byte)); }
     }
-    Some(result)
+    encoded
 }
 
-fn multiple_plugins_update_listener(app: &mut AppData) {
-    let mut candidate: Vec<String> = Vec::new();
-    loop {
-        print!("mngr > update > multi > ");
-        stdout().flush().unwrap();
-        let mut input: String = String::new();
-        stdin().read_line(&mut input).ok();
-        let input: String = input.trim_end().to_string();
-        if input.is_empty() {
-            println!("{}", "Enter plugins name those separated with ','.".yellow());
-            continue
-        }
-        for p in input.split(",").map(|c| String::from(c)) {
-            candidate.push(p.replace(" ", ""));
-        }
-        break
+fn fetch_github_releases(url: &str, asset_pattern: Option<&str>, single: bool, app: &AppData, out: &Messages) -> Option<Releases> {
+    // url: (api url)/repos/(UserName)/(RepositoryName)/releases
+    // single: the url is '/releases/latest' or '/releases/tags/(tag)' what returns one release
+    let mut builder: RequestBuilder = get_github_request_builder(url, app);
+    if !single { builder = builder.query(&[("per_page", "100")]); }
+
+    let response: Option<CachedResponse> = send_cached(builder, app, out);
+
+    if response.is_none() {
+        out.line(format!("{}", "Failed to send a request or receive a response.".yellow()));
+        return None
     }
+    let response: CachedResponse = response.unwrap();
 
-    let mut remove_candidate: Vec<String> = Vec::new();
-    for plugin in app.plugins.values() {
-        if !candidate.contains(&plugin.name) { continue };
-        let builder: RequestBuilder = get_releases_request_builder(&plugin, app);
-        let response: reqwest::Result<Response> = builder.send();
-        if response.is_err() {
-            println!("{}", "Failed to get plugin data from GitHub API.".red());
-            continue
+    match &response.status {
+        200 => (),
+        // the release is not found
+        404 if single => return Some(HashMap::new()),
+        _ => {
+            out.line(format!("{} Code: {}", "I received a not correct status code.".yellow(), &response.status));
+            out.line(format!("{}", "Check the destination of the url.".yellow()));
+            if response.status == 401 {
+                out.line(format!("\n{}", "Detected 401 error.".yellow()));
+                out.line(format!("{}", "This error means that you sent an incorrect authorization token with the request.".yellow().underline()));
+                out.line(format!("{}", "You have to check your github api token what written in 'mngr.toml' and those expiration.".yellow().underline()));
+            }
+            return None;
         }
-        let response: Response = response.unwrap();
-        let mut plugins: HashMap<DateTime<Utc>, PluginData> = response_parser(response);
-        remove_pre_release(&mut plugins);
-        remove_candidate.push(String::from(&plugin.name));
-    }
-    if !remove_candidate.is_empty() {
-        all_update(&remove_candidate, app);
     }
+    let next: Option<String> = get_next_link(&response.link);
+    let mut releases: Releases = response_parser(&response.body, asset_pattern, out);
+    fetch_next_pages(next, asset_pattern, app, &mut releases, out);
+    Some(releases)
 }
 
-fn remove_pre_release(data: &mut HashMap<DateTime<Utc>, PluginData>) {
-    let mut remove_candidate: Vec<DateTime<Utc>> = Vec::new();
-    for content in data.iter().clone() {
-        if content.1.pre_release { remove_candidate.push(content.0.clone()); }
-    }
-    if remove_candidate.is_empty() { return };
-    for key in remove_candidate {
-        data.remove(&key);
+fn fetch_github_release(pl: &PluginData, tag: Option<&str>, app: &AppData, out: &Messages) -> Option<Releases> {
+    // one release is enough without a constraint -> '/releases/latest' or '/releases/tags/(tag)' (saves the API rate limit)
+    let url: core::result::Result<String, String> = get_releases_url(&pl.repository_url, app);
+    if let Err(error) = &url {
+        out.line(format!("{}", error.red()));
+        return None
+    }
+    let url: String = get_single_release_url(&url.unwrap(), tag);
+    let releases: Releases = fetch_github_releases(&url, pl.asset_pattern.as_deref(), true, app, out)?;
+    // the latest release has no installable asset -> the list is checked
+    if releases.is_empty() && tag.is_none() { return fetch_releases(pl, app, out) };
+    Some(releases)
+}
+
+fn fetch_modrinth_releases(project: &str, asset_pattern: Option<&str>, app: &AppData, out: &Messages) -> Option<Releases> {
+    // https://docs.modrinth.com/api/operations/getproject/
+    // https://docs.modrinth.com/api/operations/getprojectversions/
+    let project_url: String = format!("{}/project/{}", &app.modrinth.api_url, project);
+    let project_json: Value = get_json(app.api.client.get(&project_url).header("User-Agent", "mngr"), "Modrinth", app, out)?;
+    let project_id: String = String::from(project_json["id"].as_str().unwrap_or(project));
+    let slug: String = String::from(project_json["slug"].as_str().unwrap_or(project));
+
+    let mut builder: RequestBuilder = app.api.client
+        .get(format!("{}/project/{}/version", &app.modrinth.api_url, &project_id))
+        .header("User-Agent", "mngr");
+    if !app.modrinth.loaders.is_empty() {
+        builder = builder.query(&[("loaders", serde_json::to_string(&app.modrinth.loaders).unwrap())]);
+    }
+    let game_versions: Vec<String> =
+        if !app.modrinth.game_versions.is_empty() { app.modrinth.game_versions.clone() }
+        else if !app.server.game_version.is_empty() { vec![String::from(&app.server.game_version)] }
+        else { Vec::new() };
+    if !game_versions.is_empty() {
+        builder = builder.query(&[("game_versions", serde_json::to_string(&game_versions).unwrap())]);
+    }
+    let versions: Value = get_json(builder, "Modrinth", app, out)?;
+
+    let mut releases: HashMap<DateTime<Utc>, PluginData> = HashMap::new();
+    for version in versions.as_array().into_iter().flatten() {
+        let date: Option<DateTime<Utc>> = version["date_published"].as_str()
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.to_utc());
+        if date.is_none() { continue };
+        let date: DateTime<Utc> = date.unwrap();
+        let mut primary: Option<String> = None;
+        let mut files: Vec<ReleaseAsset> = Vec::new();
+        for file in version["files"].as_array().into_iter().flatten() {
+            let file_name: String = String::from(file["filename"].as_str().unwrap_or(""));
+            if file["primary"].as_bool().unwrap_or(false) { primary = Some(String::from(&file_name)); }
+            files.push(ReleaseAsset {
+                id: 0,
+                name: file_name,
+                download_url: String::from(file["url"].as_str().unwrap_or("")),
+                size: file["size"].as_u64().unwrap_or(0),
+                digest: file["hashes"]["sha512"].as_str().map(|h| format!("sha512:{}", h)).unwrap_or_default(),
+                checksum_url: String::new(),
+                created_at: date.to_rfc3339(),
+            });
+        }
+        let mut candidates: Vec<ReleaseAsset> = select_assets(&files, asset_pattern, out);
+        if candidates.is_empty() { continue };
+        if asset_pattern.is_none() {
+            // the primary file is preferred
+            candidates.sort_by_key(|a| Some(&a.name) != primary.as_ref());
+        }
+        let description: Option<Vec<String>> = version["changelog"].as_str().map(|c| vec![c.replace("\r\n", "\n")]);
+        let mut plugin: PluginData = PluginData::new(
+            String::from(&slug),
+            String::from(version["version_number"].as_str().unwrap_or("")),
+            date,
+            description,
+            version["version_type"].as_str() != Some("release"),
+            String::new(),
+            format!("https://modrinth.com/plugin/{}", &slug),
+            true);
+        plugin.source = SourceKind::Modrinth;
+        plugin.project_id = String::from(&project_id);
+        plugin.game_versions = metadata_list(&version["game_versions"]);
+        plugin.platforms = metadata_list(&version["loaders"]);
+        plugin.apply_asset(&candidates[0]);
+        plugin.candidates = candidates;
+        releases.insert(date, plugin);
+    }
+    Some(releases)
+}
+
+fn fetch_hangar_releases(project: &str, asset_pattern: Option<&str>, app: &AppData, out: &Messages) -> Option<Releases> {
+    // https://hangar.papermc.io/api-docs
+    // project: (owner)/(project slug)
+    let split: Option<(&str, &str)> = project.split_once('/');
+    if split.is_none() {
+        out.line(format!("{}", "Failed to parse the given project. -> 'hangar:(owner)/(project slug)'".red()));
+        return None
+    }
+    let (owner, slug) = split.unwrap();
+    let platform: String = app.hangar.platform.to_uppercase();
+    // pages are followed until 'pagination.count' versions are listed
+    let mut versions: Vec<Value> = Vec::new();
+    let mut page: u32 = 0;
+    loop {
+        if page >= app.hangar.max_pages {
+            out.line(format!("{} {}", "Older versions are not listed. Reached 'max_pages' in 'mngr.toml':".yellow(), app.hangar.max_pages));
+            break
+        }
+        let offset: String = (page * HANGAR_PAGE_SIZE).to_string();
+        let builder: RequestBuilder = app.api.client
+            .get(format!("{}/projects/{}/versions", &app.hangar.api_url, slug))
+            .query(&[("limit", HANGAR_PAGE_SIZE.to_string().as_str()), ("offset", offset.as_str()), ("platform", platform.as_str())])
+            .header("User-Agent", "mngr");
+        let listed: Value = get_json(builder, "Hangar", app, out)?;
+        let result: Vec<Value> = listed["result"].as_array().cloned().unwrap_or_default();
+        let count: u64 = listed["pagination"]["count"].as_u64().unwrap_or(0);
+        let received: usize = result.len();
+        versions.extend(result);
+        page += 1;
+        if received == 0 || versions.len() as u64 >= count { break };
+    }
+
+    let mut releases: Releases = HashMap::new();
+    for version in &versions {
+        let date: Option<DateTime<Utc>> = version["createdAt"].as_str()
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.to_utc());
+        let download: &Value = &version["downloads"][platform.as_str()];
+        if date.is_none() || download.is_null() { continue };
+        let date: DateTime<Utc> = date.unwrap();
+        let version_name: String = String::from(version["name"].as_str().unwrap_or(""));
+        if download["downloadUrl"].as_str().is_none() {
+            // the file is hosted on an external site, so mngr cannot download it.
+            if let Some(external) = download["externalUrl"].as_str() {
+                out.line(format!("{} {} -> {}", "External download (skipped):".yellow(), &version_name, external));
+            }
+            continue
+        }
+        let file: ReleaseAsset = ReleaseAsset {
+            id: 0,
+            name: String::from(download["fileInfo"]["name"].as_str().unwrap_or("")),
+            download_url: String::from(download["downloadUrl"].as_str().unwrap()),
+            size: download["fileInfo"]["sizeBytes"].as_u64().unwrap_or(0),
+            digest: download["fileInfo"]["sha256Hash"].as_str().map(|h| format!("sha256:{}", h)).unwrap_or_default(),
+            checksum_url: String::new(),
+            created_at: date.to_rfc3339(),
+        };
+        let candidates: Vec<ReleaseAsset> = select_assets(&[file], asset_pattern, out);
+        if candidates.is_empty() { continue };
+        let description: Option<Vec<String>> = version["description"].as_str().map(|d| vec![d.replace("\r\n", "\n")]);
+        let mut plugin: PluginData = PluginData::new(
+            String::from(slug),
+            version_name,
+            date,
+            description,
+            version["channel"]["name"].as_str() != Some("Release"),
+            String::new(),
+            format!("https://hangar.papermc.io/{}/{}", owner, slug),
+            true);
+        plugin.source = SourceKind::Hangar;
+        plugin.project_id = String::from(project);
+        plugin.game_versions = metadata_list(&version["platformDependencies"][platform.as_str()]);
+        plugin.platforms = vec![platform.to_lowercase()];
+        plugin.apply_asset(&candidates[0]);
+        plugin.candidates = candidates;
+        releases.insert(date, plugin);
+    }
+    Some(releases)
+}
+
+fn fetch_spiget_releases(resource: &str, app: &AppData, out: &Messages) -> Option<Releases> {
+    // https://spiget.org/documentation/
+    if resource.is_empty() || !resource.chars().all(|c| c.is_ascii_digit()) {
+        out.line(format!("{}", "Failed to parse the given resource. -> 'spigot:(resource id)'".red()));
+        return None
+    }
+    let resource_url: String = format!("{}/resources/{}", &app.spiget.api_url, resource);
+    let resource_json: Value = get_json(app.api.client.get(&resource_url).header("User-Agent", "mngr"), "Spiget", app, out)?;
+    let name: String = resource_json["name"].as_str().unwrap_or(resource).trim().to_string();
+    if resource_json["premium"].as_bool().unwrap_or(false) {
+        out.line(format!("{} {}", "Premium resources cannot be downloaded by mngr:".red(), &name));
+        return None
+    }
+    if resource_json["external"].as_bool().unwrap_or(false) || resource_json["file"]["type"].as_str() == Some("external") {
+        // the resource is hosted on an external site, so mngr cannot download it.
+        out.line(format!("{} {}", "The resource is downloaded from an external site:".red(), &name));
+        out.line(format!("{} {}", "External URL:".yellow(), resource_json["file"]["externalUrl"].as_str().unwrap_or("UNKNOWN")));
+        out.line(format!("{}", "Download it by hand and place it into 'plugins'.".yellow()));
+        return None
+    }
+    let latest_id: u64 = resource_json["version"]["id"].as_u64().unwrap_or(0);
+
+    let builder: RequestBuilder = app.api.client
+        .get(format!("{}/versions", &resource_url))
+        .query(&[("size", "100"), ("sort", "-releaseDate")])
+        .header("User-Agent", "mngr");
+    let versions: Value = get_json(builder, "Spiget", app, out)?;
+
+    // file names are not provided by Spiget, so mngr names them '(resource name)-(version).jar'
+    let base_name: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' }).collect();
+    let mut releases: Releases = HashMap::new();
+    for version in versions.as_array().into_iter().flatten() {
+        let date: Option<DateTime<Utc>> = version["releaseDate"].as_i64().and_then(|d| DateTime::from_timestamp(d, 0));
+        let version_id: Option<u64> = version["id"].as_u64();
+        if date.is_none() || version_id.is_none() { continue };
+        let version_name: String = String::from(version["name"].as_str().unwrap_or(""));
+        // older versions are listed, but Spiget serves only the latest file
+        // ('/versions/(id)/download' redirects to the download page of spigotmc.org, not to a jar)
+        let download_url: String =
+            if version_id.unwrap() == latest_id { format!("{}/download", &resource_url) }
+            else { String::new() };
+        let mut plugin: PluginData = PluginData::new(
+            String::from(&name),
+            String::from(&version_name),
+            date.unwrap(),
+            None,
+            false,
+            format!("{}-{}.jar", &base_name, version_name.replace(['/', '\\', ' '], "_")),
+            format!("https://www.spigotmc.org/resources/{}/", resource),
+            true);
+        plugin.source = SourceKind::Spigot;
+        plugin.project_id = String::from(resource);
+        plugin.asset_id = version_id.unwrap();
+        plugin.download_url = download_url;
+        releases.insert(date.unwrap(), plugin);
+    }
+    Some(releases)
+}
+
+fn fetch_jenkins_releases(job: &str, asset_pattern: Option<&str>, app: &AppData, out: &Messages) -> Option<Releases> {
+    // https://www.jenkins.io/doc/book/using/remote-access-api/
+    // job: https://ci.example.com/job/(job name)
+    if !job.starts_with("http://") && !job.starts_with("https://") {
+        out.line(format!("{}", "Failed to parse the given job url. -> 'jenkins:(job url)'".red()));
+        return None
+    }
+    let job: &str = job.trim_end_matches('/');
+    let builder: RequestBuilder = app.api.client
+        .get(format!("{}/api/json", job))
+        .query(&[("tree", "name,builds[number,result,timestamp,artifacts[fileName,relativePath]]")])
+        .header("User-Agent", "mngr");
+    let job_json: Value = get_json(builder, "Jenkins", app, out)?;
+    let name: String = String::from(job_json["name"].as_str().unwrap_or_else(|| job.rsplit('/').next().unwrap()));
+
+    let mut releases: Releases = HashMap::new();
+    for build in job_json["builds"].as_array().into_iter().flatten() {
+        if build["result"].as_str() != Some("SUCCESS") { continue };
+        let number: Option<u64> = build["number"].as_u64();
+        let date: Option<DateTime<Utc>> = build["timestamp"].as_i64().and_then(DateTime::from_timestamp_millis);
+        if number.is_none() || date.is_none() { continue };
+        let (number, date) = (number.unwrap(), date.unwrap());
+        let artifacts: Vec<ReleaseAsset> = build["artifacts"].as_array().into_iter().flatten()
+            .map(|artifact| ReleaseAsset {
+                id: number,
+                name: String::from(artifact["fileName"].as_str().unwrap_or("")),
+                download_url: format!("{}/{}/artifact/{}", job, number, artifact["relativePath"].as_str().unwrap_or("")),
+                size: 0,
+                digest: String::new(),
+                checksum_url: String::new(),
+                created_at: date.to_rfc3339(),
+            })
+            .collect();
+        let candidates: Vec<ReleaseAsset> = select_assets(&artifacts, asset_pattern, out);
+        if candidates.is_empty() { continue };
+        let mut plugin: PluginData = PluginData::new(
+            String::from(&name),
+            number.to_string(),
+            date,
+            None,
+            false,
+            String::new(),
+            String::from(job),
+            true);
+        plugin.source = SourceKind::Jenkins;
+        plugin.project_id = String::from(job);
+        plugin.apply_asset(&candidates[0]);
+        plugin.candidates = candidates;
+        releases.insert(date, plugin);
+    }
+    Some(releases)
+}
+
+fn fetch_actions_releases(artifact: &str, app: &AppData, out: &Messages) -> Option<Releases> {
+    // https://docs.github.com/en/rest/actions/workflow-runs
+    // https://docs.github.com/en/rest/actions/artifacts
+    // artifact: (repository url)#(branch)/(artifact name)
+    let parsed: Option<(&str, &str, &str)> = artifact.split_once('#')
+        .and_then(|(url, fragment)| fragment.rsplit_once('/').map(|(branch, name)| (url, branch, name)));
+    if parsed.is_none() {
+        out.line(format!("{}", "Failed to parse the given artifact. -> 'actions:(repository url)#(branch)/(artifact name)'".red()));
+        return None
+    }
+    let (url, branch, artifact_name) = parsed.unwrap();
+    let repository: core::result::Result<(String, String), String> = get_github_repository(url, app);
+    if let Err(error) = &repository {
+        out.line(format!("{}", error.red()));
+        return None
+    }
+    let (author, repository_name) = repository.unwrap();
+    if app.github_token.is_empty() {
+        out.line(format!("{}", "Artifacts of GitHub Actions can be downloaded only with 'github_token' in 'mngr.toml'.".yellow()));
+    }
+    // two requests: the successful runs on the branch, and the artifacts of all runs (the newest first).
+    // ('workflow_run' of an artifact has no conclusion, so only the artifacts of the successful runs are used)
+    let api_url: String = format!("{}/repos/{}/{}/actions", app.github.api_url.trim_end_matches('/'), &author, &repository_name);
+    let builder: RequestBuilder = get_github_request_builder(&format!("{}/runs", &api_url), app)
+        .query(&[("branch", branch), ("status", "success"), ("per_page", "100")]);
+    let runs: Value = get_json(builder, "GitHub Actions", app, out)?;
+    let succeeded: Vec<u64> = runs["workflow_runs"].as_array().into_iter().flatten()
+        .filter_map(|run| run["id"].as_u64())
+        .collect();
+    let builder: RequestBuilder = get_github_request_builder(&format!("{}/artifacts", &api_url), app)
+        .query(&[("name", artifact_name), ("per_page", "100")]);
+    let artifacts: Value = get_json(builder, "GitHub Actions", app, out)?;
+
+    let mut releases: Releases = HashMap::new();
+    for found in artifacts["artifacts"].as_array().into_iter().flatten() {
+        let run: &Value = &found["workflow_run"];
+        if run["head_branch"].as_str() != Some(branch) || found["expired"].as_bool().unwrap_or(false) { continue };
+        let id: Option<u64> = run["id"].as_u64().filter(|id| succeeded.contains(id));
+        let date: Option<DateTime<Utc>> = found["created_at"].as_str()
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.to_utc());
+        if id.is_none() || date.is_none() { continue };
+        let (id, date) = (id.unwrap(), date.unwrap());
+        // the archive is extracted when it is downloaded, so the jar is saved with the artifact name.
+        let asset: ReleaseAsset = ReleaseAsset {
+            id: found["id"].as_u64().unwrap_or(0),
+            name: format!("{}.jar", artifact_name.strip_suffix(".jar").unwrap_or(artifact_name)),
+            download_url: String::from(found["archive_download_url"].as_str().unwrap_or("")),
+            size: 0,
+            digest: String::from(found["digest"].as_str().unwrap_or("")),
+            checksum_url: String::new(),
+            created_at: date.to_rfc3339(),
+        };
+        let description: Option<Vec<String>> = run["head_sha"].as_str().map(|sha| vec![format!("commit {}", sha)]);
+        let mut plugin: PluginData = PluginData::new(
+            String::from(&repository_name),
+            id.to_string(),
+            date,
+            description,
+            false,
+            String::new(),
+            format!("{}/{}/{}", app.github.web_url.trim_end_matches('/'), &author, &repository_name),
+            true);
+        plugin.source = SourceKind::Actions;
+        plugin.project_id = format!("{}/{}", branch, artifact_name);
+        plugin.apply_asset(&asset);
+        plugin.candidates = vec![asset];
+        releases.insert(date, plugin);
+    }
+    Some(releases)
+}
+
+fn extract_jar(archive: &[u8], asset_pattern: Option<&str>, out: &Messages) -> Option<Vec<u8>> {
+    // an artifact of GitHub Actions is a zip archive what contains the uploaded files.
+    let archive: zip::result::ZipResult<ZipArchive<Cursor<&[u8]>>> = ZipArchive::new(Cursor::new(archive));
+    if archive.is_err() {
+        out.line(format!("{}", "Failed to open the artifact archive.".red()));
+        return None
+    }
+    let mut archive: ZipArchive<Cursor<&[u8]>> = archive.unwrap();
+    let entries: Vec<ReleaseAsset> = archive.file_names()
+        .map(|name| ReleaseAsset { name: String::from(name), ..Default::default() })
+        .collect();
+    let candidates: Vec<ReleaseAsset> = select_assets(&entries, asset_pattern, out);
+    if candidates.is_empty() {
+        out.line(format!("{}", "No jar is found in the artifact archive.".red()));
+        return None
+    }
+    let mut content: Vec<u8> = Vec::new();
+    let read: bool = archive.by_name(&candidates[0].name)
+        .is_ok_and(|mut entry| entry.read_to_end(&mut content).is_ok());
+    if !read {
+        out.line(format!("{} {}", "Failed to extract".red(), &candidates[0].name.underline()));
+        return None
+    }
+    Some(content)
+}
+
+fn register_url(app: &mut AppData, link: &str) -> bool {
+    let releases: Option<Releases> = fetch_url_releases(link, None, app, &Messages::printed());
+    if releases.is_none() { return false };
+    let plugin: PluginData = releases.unwrap().into_values().next().unwrap();
+    if app.plugins.contains_key(&plugin.name) {
+        println!("{}", "The plugin has already registered.".yellow());
+        println!("{}", app.plugins.get(&plugin.name).unwrap().content());
+        return false
+    }
+    println!("{}", "The plugin has been successfully registered.".green());
+    println!("{}", plugin.content());
+    app.plugins.insert(String::from(&plugin.name), plugin);
+    true
+}
+
+fn fetch_url_releases(link: &str, current: Option<&PluginData>, app: &AppData, out: &Messages) -> Option<Releases> {
+    // a direct link has only one "release", and its version is changed when the file is changed.
+    if !link.starts_with("https://") && !link.starts_with("http://") {
+        out.line(format!("{}", "Failed to parse the given url. -> 'url:(https link to a jar)'".red()));
+        return None
+    }
+    let mut builder: RequestBuilder = app.api.client.head(link).header("User-Agent", "mngr");
+    if let Some(current) = current {
+        if !current.etag.is_empty() { builder = builder.header("If-None-Match", &current.etag); }
+        if !current.last_modified.is_empty() { builder = builder.header("If-Modified-Since", &current.last_modified); }
+    }
+    let response: core::result::Result<Response, String> = send_request(builder, app, out);
+    if response.is_err() {
+        out.line(format!("{} {}", "Failed to send a request or receive a response.".red(), link));
+        return None
+    }
+    let response: Response = response.unwrap();
+    let mut releases: Releases = HashMap::new();
+    if let (304, Some(current)) = (response.status().as_u16(), current) {
+        // not modified
+        let current: PluginData = current.clone();
+        releases.insert(DateTime::parse_from_rfc3339(&current.introduced_date).map(|d| d.to_utc()).unwrap_or(DateTime::<Utc>::MIN_UTC), current);
+        return Some(releases)
+    }
+    if !response.status().is_success() {
+        out.line(format!("{} Code: {} From: {}", "I received a not correct status code.".red(), response.status().as_u16(), link));
+        return None
+    }
+    let header = |key: &str| -> String {
+        response.headers().get(key).and_then(|v| v.to_str().ok()).map(String::from).unwrap_or_default()
+    };
+    let etag: String = header("ETag");
+    let last_modified: String = header("Last-Modified");
+    let date: DateTime<Utc> = DateTime::parse_from_rfc2822(&last_modified).map(|d| d.to_utc()).unwrap_or_else(|_| Utc::now());
+    let version: String =
+        if !last_modified.is_empty() { date.format("%Y%m%d-%H%M%S").to_string() }
+        else if !etag.is_empty() { etag.chars().filter(|c| c.is_ascii_alphanumeric()).take(16).collect() }
+        else {
+            out.line(format!("{}", "The server sends neither ETag nor Last-Modified, so every update downloads the file again.".yellow()));
+            Utc::now().format("%Y%m%d-%H%M%S").to_string()
+        };
+    let file_name: String = match current {
+        Some(current) => String::from(&current.file_name),
+        None => {
+            let last: &str = link.split(['?', '#']).next().unwrap().rsplit('/').next().unwrap_or("");
+            if last.is_empty() { String::from("plugin.jar") } else { String::from(last) }
+        }
+    };
+    let name: String = match current {
+        Some(current) => String::from(&current.name),
+        None => String::from(file_name.strip_suffix(".jar").unwrap_or(&file_name)),
+    };
+    let mut plugin: PluginData = PluginData::new(name, version, date, None, false, file_name, String::from(link), true);
+    plugin.source = SourceKind::Url;
+    plugin.project_id = String::from(link);
+    plugin.download_url = String::from(link);
+    plugin.size = header("Content-Length").parse::<u64>().unwrap_or(0);
+    plugin.etag = etag;
+    plugin.last_modified = last_modified;
+    releases.insert(date, plugin);
+    Some(releases)
+}
+
+fn register_file(app: &mut AppData, path: &str) -> bool {
+    // the jar is tracked in mngr.toml and mngr.lock, but never updated automatically.
+    let source: PathBuf = PathBuf::from(path);
+    let content: Result<Vec<u8>> = fs::read(source.as_path());
+    if content.is_err() || source.file_name().is_none() {
+        println!("{} {}", "Failed to read the file.".red(), path);
+        return false
+    }
+    let content: Vec<u8> = content.unwrap();
+    if !content.starts_with(b"PK\x03\x04") {
+        println!("{} {}", "The file is not a valid jar.".red(), path);
+        return false
+    }
+    let file_name: String = source.file_name().unwrap().to_string_lossy().to_string();
+    let name: String = String::from(file_name.strip_suffix(".jar").unwrap_or(&file_name));
+    if app.plugins.contains_key(&name) {
+        println!("{}", "The plugin has already registered.".yellow());
+        println!("{}", app.plugins.get(&name).unwrap().content());
+        return false
+    }
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    if directory.is_none() {
+        println!("{}", "Failed to get 'plugins' directory's path.".red());
+        return false
+    }
+    let mut target: PathBuf = directory.unwrap();
+    target.push(&file_name);
+    let absolute: PathBuf = fs::canonicalize(source.as_path()).unwrap_or(source);
+    let placed: bool = fs::canonicalize(target.as_path()).is_ok_and(|t| t == absolute);
+    if !placed {
+        // copies the jar into 'plugins'
+        if target.exists() {
+            println!("{} {}", "The file has already exists in 'plugins'.".red(), &file_name);
+            return false
+        }
+        if !place_jar(&file_name, &content, None) {
+            println!("{}", "Failed to copy the file into 'plugins'.".red());
+            return false
+        }
+    }
+    let location: String = format!("file://{}", absolute.to_string_lossy());
+    let mut plugin: PluginData = PluginData::new(name, sha256_hex(&content)[..12].to_string(), Utc::now(), None, false, file_name, String::from(&location), false);
+    plugin.source = SourceKind::File;
+    plugin.project_id = absolute.to_string_lossy().to_string();
+    plugin.download_url = String::from(&location);
+    plugin.size = content.len() as u64;
+    lock_plugin(&plugin, &location, &content);
+    println!("{}", "The plugin has been successfully registered.".green());
+    println!("{}", plugin.content());
+    app.plugins.insert(String::from(&plugin.name), plugin);
+    true
+}
+
+fn get_forge<'a>(url: &str, app: &'a AppData) -> Option<&'a ForgeHost> {
+    // the longest 'base_url' what the url starts with
+    app.forges.iter()
+        .filter(|f| url.starts_with(f.base_url.trim_end_matches('/')))
+        .max_by_key(|f| f.base_url.len())
+}
+
+fn split_forge_url(url: &str, app: &AppData) -> Option<(String, String)> {
+    // https://git.example.com/(owner)/(repository) -> ('https://git.example.com', '(owner)/(repository)')
+    let url: &str = url.trim_end_matches('/');
+    let url: &str = url.strip_suffix(".git").unwrap_or(url);
+    let base: String = match get_forge(url, app) {
+        Some(forge) => String::from(forge.base_url.trim_end_matches('/')),
+        None => {
+            let (scheme, rest) = url.split_once("://")?;
+            format!("{}://{}", scheme, rest.split('/').next()?)
+        }
+    };
+    let path: &str = url.strip_prefix(base.as_str())?.trim_start_matches('/');
+    if !path.contains('/') { return None };
+    Some((base, String::from(path)))
+}
+
+fn forge_request(url: &str, app: &AppData) -> RequestBuilder {
+    let mut builder: RequestBuilder = app.api.client.get(url).header("User-Agent", "mngr");
+    if let Some(forge) = get_forge(url, app).filter(|f| !f.token.is_empty()) {
+        builder = builder.header("Authorization", format!("Bearer {}", &forge.token));
+    }
+    builder
+}
+
+fn fetch_gitlab_releases(project_url: &str, asset_pattern: Option<&str>, app: &AppData, out: &Messages) -> Option<Releases> {
+    // https://docs.gitlab.com/ee/api/releases/
+    let split: Option<(String, String)> = split_forge_url(project_url, app);
+    if split.is_none() {
+        out.line(format!("{}", "Failed to parse the given url. -> 'gitlab:(project url)'".red()));
+        return None
+    }
+    let (base, path) = split.unwrap();
+    let url: String = format!("{}/api/v4/projects/{}/releases", &base, path.replace('/', "%2F"));
+    let pages: Vec<Value> = get_forge_pages(&url, "GitLab", app, out)?;
+    let name: String = String::from(path.rsplit('/').next().unwrap());
+
+    let mut releases: Releases = HashMap::new();
+    for release in pages.iter().flat_map(|page| page.as_array().into_iter().flatten()) {
+        let date: Option<DateTime<Utc>> = release["released_at"].as_str()
+            .or(release["created_at"].as_str())
+            .and_then(|d| DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.to_utc());
+        if date.is_none() { continue };
+        let date: DateTime<Utc> = date.unwrap();
+        let links: Vec<ReleaseAsset> = release["assets"]["links"].as_array().into_iter().flatten()
+            .map(|link| ReleaseAsset {
+                id: link["id"].as_u64().unwrap_or(0),
+                name: String::from(link["name"].as_str().unwrap_or("")),
+                download_url: String::from(link["direct_asset_url"].as_str().or(link["url"].as_str()).unwrap_or("")),
+                size: 0,
+                digest: String::new(),
+                checksum_url: String::new(),
+                created_at: date.to_rfc3339(),
+            })
+            .collect();
+        let candidates: Vec<ReleaseAsset> = select_assets(&links, asset_pattern, out);
+        if candidates.is_empty() { continue };
+        let description: Option<Vec<String>> = release["description"].as_str().map(|d| vec![d.replace("\r\n", "\n")]);
+        let mut plugin: PluginData = PluginData::new(
+            String::from(&name),
+            String::from(release["tag_name"].as_str().unwrap_or("")),
+            date,
+            description,
+            release["upcoming_release"].as_bool().unwrap_or(false),
+            String::new(),
+            format!("{}/{}", &base, &path),
+            true);
+        plugin.source = SourceKind::GitLab;
+        plugin.project_id = String::from(&path);
+        plugin.apply_asset(&candidates[0]);
+        plugin.candidates = candidates;
+        releases.insert(date, plugin);
+    }
+    Some(releases)
+}
+
+fn fetch_gitea_releases(repository_url: &str, asset_pattern: Option<&str>, app: &AppData, out: &Messages) -> Option<Releases> {
+    // https://docs.gitea.com/api/ (Forgejo has the same API)
+    let split: Option<(String, String)> = split_forge_url(repository_url, app);
+    if split.is_none() {
+        out.line(format!("{}", "Failed to parse the given url. -> 'gitea:(repository url)'".red()));
+        return None
+    }
+    let (base, path) = split.unwrap();
+    let url: String = format!("{}/api/v1/repos/{}/releases", &base, &path);
+    let pages: Vec<Value> = get_forge_pages(&url, "Gitea", app, out)?;
+    let name: String = String::from(path.rsplit('/').next().unwrap());
+
+    let mut releases: Releases = HashMap::new();
+    for release in pages.iter().flat_map(|page| page.as_array().into_iter().flatten()) {
+        if release["draft"].as_bool().unwrap_or(false) { continue };
+        let assets: Vec<ReleaseAsset> = release["assets"].as_array().into_iter().flatten()
+            .map(|asset| ReleaseAsset {
+                id: asset["id"].as_u64().unwrap_or(0),
+                name: String::from(asset["name"].as_str().unwrap_or("")),
+                download_url: String::from(asset["browser_download_url"].as_str().unwrap_or("")),
+                size: asset["size"].as_u64().unwrap_or(0),
+                digest: String::new(),
+                checksum_url: String::new(),
+                created_at: String::from(asset["created_at"].as_str().or(release["published_at"].as_str()).unwrap_or("")),
+            })
+            .collect();
+        let candidates: Vec<ReleaseAsset> = select_assets(&assets, asset_pattern, out);
+        if candidates.is_empty() { continue };
+        let date: Option<DateTime<Utc>> = DateTime::parse_from_rfc3339(&candidates[0].created_at).ok().map(|d| d.to_utc());
+        if date.is_none() { continue };
+        let date: DateTime<Utc> = date.unwrap();
+        let description: Option<Vec<String>> = release["body"].as_str().map(|b| vec![b.replace("\r\n", "\n")]);
+        let mut plugin: PluginData = PluginData::new(
+            String::from(&name),
+            String::from(release["tag_name"].as_str().unwrap_or("")),
+            date,
+            description,
+            release["prerelease"].as_bool().unwrap_or(false),
+            String::new(),
+            format!("{}/{}", &base, &path),
+            true);
+        plugin.source = SourceKind::Gitea;
+        plugin.project_id = String::from(&path);
+        plugin.apply_asset(&candidates[0]);
+        plugin.candidates = candidates;
+        releases.insert(date, plugin);
+    }
+    Some(releases)
+}
+
+fn get_forge_pages(url: &str, service: &str, app: &AppData, out: &Messages) -> Option<Vec<Value>> {
+    // GitLab -> 'per_page' and 'X-Next-Page', Gitea -> 'limit' and 'Link' (rel="next")
+    // follows the next pages until 'max_pages' of the host (including the first page) is reached
+    let max_pages: u32 = get_forge(url, app).map_or(default_max_pages(), |f| f.max_pages);
+    let size: &str = if service == "GitLab" { "per_page" } else { "limit" };
+    let mut builder: RequestBuilder = forge_request(url, app).query(&[(size, "100")]);
+    let mut pages: Vec<Value> = Vec::new();
+    loop {
+        let (json, response) = get_json_response(builder, service, app, out)?;
+        pages.push(json);
+        let next: Option<String> =
+            if !response.next_page.is_empty() { Some(format!("{}?{}=100&page={}", url, size, &response.next_page)) }
+            else { get_next_link(&response.link) };
+        if next.is_none() { break };
+        if pages.len() as u32 >= max_pages {
+            out.line(format!("{} {}", "Older releases are not listed. Reached 'max_pages' in 'mngr.toml':".yellow(), max_pages));
+            break
+        }
+        builder = forge_request(&next.unwrap(), app);
+    }
+    Some(pages)
+}
+
+fn get_next_link(link: &str) -> Option<String> {
+    // Link: <(api url)/repositories/(id)/releases?per_page=100&page=2>; rel="next", <...>; rel="last"
+    let next: &str = link.split(',').find(|part| part.contains("rel=\"next\""))?;
+    let (_, rest) = next.split_once('<')?;
+    let (url, _) = rest.split_once('>')?;
+    Some(String::from(url))
+}
+
+fn get_json(builder: RequestBuilder, service: &str, app: &AppData, out: &Messages) -> Option<Value> {
+    get_json_response(builder, service, app, out).map(|(json, _)| json)
+}
+
+fn get_json_response(builder: RequestBuilder, service: &str, app: &AppData, out: &Messages) -> Option<(Value, CachedResponse)> {
+    // the response is returned too (e.g. for the pagination headers)
+    let response: Option<CachedResponse> = send_cached(builder, app, out);
+    if response.is_none() {
+        out.line(format!("{} ({})", "Failed to send a request or receive a response.".red(), service));
+        return None
+    }
+    let response: CachedResponse = response.unwrap();
+    if !(200..300).contains(&response.status) {
+        out.line(format!("{} Code: {} ({})", "I received a not correct status code.".red(), response.status, service));
+        return None
+    }
+    match serde_json::from_str::<Value>(response.body.as_str()) {
+        Ok(json) => Some((json, response)),
+        _ => {
+            out.line(format!("{} ({})", "Failed to parse the response.".red(), service));
+            None
+        }
+    }
+}
+
+fn send_cached(builder: RequestBuilder, app: &AppData, out: &Messages) -> Option<CachedResponse> {
+    // the response is cached in '.mngr/cache', and sent again with 'If-None-Match' / 'If-Modified-Since'.
+    // (304 responses do not count against the GitHub API rate limit)
+    let url: String = builder.try_clone()
+        .and_then(|b| b.build().ok())
+        .map(|request| request.url().to_string())
+        .unwrap_or_default();
+    let path: Option<PathBuf> = get_cache_path(&url);
+    let cached: Option<CachedResponse> = path.as_ref()
+        .and_then(|p| fs::read_to_string(p).ok())
+        .and_then(|text| serde_json::from_str::<CachedResponse>(&text).ok());
+    if app.offline {
+        if cached.is_none() { out.line(format!("{} {}", "Not cached (offline):".yellow(), &url)) };
+        return cached.map(|c| CachedResponse { status: 200, ..c })
+    }
+
+    let mut builder: RequestBuilder = builder;
+    if let Some(cached) = &cached {
+        if !cached.etag.is_empty() { builder = builder.header("If-None-Match", &cached.etag) };
+        if !cached.last_modified.is_empty() { builder = builder.header("If-Modified-Since", &cached.last_modified) };
+    }
+    let response: Response = send_request(builder, app, out).ok()?;
+    if response.status().as_u16() == 304 && cached.is_some() {
+        // the modified time is the last use of the cache (see 'prune_cache')
+        if let Some(path) = &path { File::options().write(true).open(path).and_then(|f| f.set_modified(SystemTime::now())).ok(); }
+        return cached.map(|c| CachedResponse { status: 200, ..c })
+    }
+    let header = |name: &str| -> String {
+        response.headers().get(name).and_then(|v| v.to_str().ok()).map(String::from).unwrap_or_default()
+    };
+    let (etag, last_modified, link, next_page) = (header("ETag"), header("Last-Modified"), header("Link"), header("X-Next-Page"));
+    let status: u16 = response.status().as_u16();
+    let fresh: CachedResponse = CachedResponse { url, etag, last_modified, link, next_page, body: response.text().ok()?, status };
+    if status == 200 {
+        if let Some(path) = path {
+            fs::create_dir_all(path.parent().unwrap()).ok();
+            fs::write(path, serde_json::to_string(&fresh).unwrap()).ok();
+        }
+    }
+    Some(fresh)
+}
+
+fn send_request(builder: RequestBuilder, app: &AppData, out: &Messages) -> core::result::Result<Response, String> {
+    // every request goes through here. 5xx and rate limited responses are retried with exponential backoff.
+    if app.offline {
+        let url: String = builder.build().map(|request| request.url().to_string()).unwrap_or_default();
+        out.line(format!("{} {}", "Not sent (offline):".yellow(), &url));
+        return Err(String::from("offline"))
+    }
+    let mut builder: RequestBuilder = builder;
+    let mut attempt: u32 = 0;
+    loop {
+        let next: Option<RequestBuilder> = builder.try_clone();
+        app.api.usage.lock().unwrap().calls += 1;
+        let response: Response = builder.send().map_err(|e| e.to_string())?;
+        record_usage(&response, app);
+        let from_api: bool = response.url().as_str().starts_with(app.github.api_url.trim_end_matches('/'));
+        let wait: Option<u64> = get_retry_wait(response.status().as_u16(), response.headers(), from_api, attempt, app);
+        match (wait, next) {
+            (Some(wait), Some(next)) if attempt < MAX_RETRIES => {
+                out.line(format!("{} {}s (Code: {})", "Retrying in".yellow(), wait, response.status().as_u16()));
+                thread::sleep(Duration::from_secs(wait));
+                app.api.usage.lock().unwrap().retries += 1;
+                builder = next;
+                attempt += 1;
+            },
+            _ => return Ok(response),
+        }
+    }
+}
+
+fn record_usage(response: &Response, app: &AppData) {
+    let mut usage = app.api.usage.lock().unwrap();
+    if response.status().as_u16() == 304 { usage.not_modified += 1; }
+    // other services (e.g. Modrinth) use the same header names in other meanings
+    if !response.url().as_str().starts_with(app.github.api_url.trim_end_matches('/')) { return };
+    let header = |name: &str| -> Option<i64> { response.headers().get(name)?.to_str().ok()?.parse::<i64>().ok() };
+    if let Some(remaining) = header("X-RateLimit-Remaining") { usage.remaining = Some(remaining); }
+    if let Some(reset) = header("X-RateLimit-Reset") { usage.reset = Some(reset); }
+}
+
+fn get_retry_wait(status: u16, headers: &HeaderMap, from_api: bool, attempt: u32, app: &AppData) -> Option<u64> {
+    // seconds to wait before the retry (None -> not retried)
+    // from_api: the response is from 'api_url' (other services use 'X-RateLimit-Reset' in other meanings)
+    let header = |name: &str| -> Option<i64> { headers.get(name)?.to_str().ok()?.parse::<i64>().ok() };
+    let backoff: u64 = 1 << attempt;
+    if status == 403 || status == 429 {
+        // secondary rate limit -> 'Retry-After', primary rate limit -> 'X-RateLimit-Remaining: 0' until 'X-RateLimit-Reset'
+        let wait: Option<u64> =
+            if let Some(after) = header("Retry-After") { Some(after.max(1) as u64) }
+            else if from_api && header("X-RateLimit-Remaining") == Some(0) { header("X-RateLimit-Reset").map(|reset| (reset - Utc::now().timestamp()).max(1) as u64) }
+            else if status == 429 { Some(backoff) }
+            else { None };
+        return wait.filter(|wait| *wait <= app.github.max_wait)
+    }
+    if (500..600).contains(&status) {
+        return Some(header("Retry-After").map_or(backoff, |after| after.max(1) as u64)).filter(|wait| *wait <= app.github.max_wait)
+    }
+    None
+}
+
+fn ensure_rate_limit(app: &AppData, out: &Messages) -> bool {
+    // called before each plugin of a batch update, so the batch never exhausts the GitHub API rate limit.
+    let usage: ApiUsage = *app.api.usage.lock().unwrap();
+    let (remaining, reset) = match (usage.remaining, usage.reset) {
+        (Some(remaining), Some(reset)) => (remaining, reset),
+        _ => return true,
+    };
+    let wait: i64 = reset - Utc::now().timestamp();
+    if remaining > app.github.min_remaining || wait <= 0 { return true };
+    if wait as u64 > app.github.max_wait {
+        out.line(format!("{} {}", "Stopped before exhausting the API rate limit. Remaining:".red(), remaining));
+        out.line(format!("The time when the API rate limit is lifted. (UTC) = {}", DateTime::from_timestamp(reset, 0).unwrap()));
+        return false
+    }
+    // printed at once, also on worker threads (the wait is not silent)
+    println!("{} {}s", "Waiting for the API rate limit reset:".yellow(), wait);
+    thread::sleep(Duration::from_secs(wait as u64 + 1));
+    app.api.usage.lock().unwrap().remaining = None;
+    true
+}
+
+fn report_api_usage(app: &AppData) {
+    let mut usage = app.api.usage.lock().unwrap();
+    if usage.calls == 0 { return };
+    let remaining: String = usage.remaining.map_or(String::from("UNKNOWN"), |r| r.to_string());
+    println!("\nRequests: {} (not modified: {}, retried: {}) / GitHub API remaining: {}", usage.calls, usage.not_modified, usage.retries, remaining);
+    usage.calls = 0;
+    usage.not_modified = 0;
+    usage.retries = 0;
+}
+
+fn prune_cache(app: &AppData) {
+    // removes the cached responses what are not used for 'CACHE_MAX_AGE'
+    if app.offline { return };
+    let directory: Option<PathBuf> = get_cache_path("_").and_then(|p| p.parent().map(Path::to_path_buf));
+    let entries: Option<fs::ReadDir> = directory.and_then(|d| fs::read_dir(d).ok());
+    if entries.is_none() { return };
+    for entry in entries.unwrap().flatten() {
+        let age: Option<Duration> = entry.metadata().and_then(|m| m.modified()).ok()
+            .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+        if age.is_some_and(|age| age > CACHE_MAX_AGE) { fs::remove_file(entry.path()).ok(); }
+    }
+}
+
+fn get_cache_path(url: &str) -> Option<PathBuf> {
+    // .mngr/cache/(sha256 of the request url).json
+    if url.is_empty() { return None };
+    let mut current: PathBuf = env::current_dir().ok()?;
+    current.push(".mngr");
+    current.push("cache");
+    current.push(format!("{}.json", sha256_hex(url.as_bytes())));
+    Some(current)
+}
+
+fn get_plugins_directory_path() -> Option<PathBuf> {
+    let mut current: PathBuf = match env::current_dir() {
+        Ok(path) => path,
+        _ => return None,
+    };
+    current.push("plugins");
+    Some(current)
+}
+
+struct SyncStatus {
+    // jar files that are placed in 'plugins' but not registered
+    orphans: Vec<String>,
+    // names of registered plugins whose jar file is not placed in 'plugins'
+    missing: Vec<String>,
+}
+
+fn get_sync_status(app: &AppData) -> Option<SyncStatus> {
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    if directory.is_none() {
+        println!("{}", "Failed to get 'plugins' directory's path.".red());
+        return None
+    }
+    let entries = fs::read_dir(directory.unwrap());
+    if entries.is_err() {
+        println!("{}", "Failed to read 'plugins' directory.".red());
+        println!("{}", "Change the current directory or make a directory that named 'plugins' here and retry it.".red());
+        return None
+    }
+    let mut jars: Vec<String> = Vec::new();
+    for entry in entries.unwrap().flatten() {
+        if !entry.path().is_file() { continue };
+        let name: String = entry.file_name().to_string_lossy().to_string();
+        if name.ends_with(".jar") { jars.push(name); }
+    }
+
+    let mut orphans: Vec<String> = jars.iter()
+        .filter(|jar| !app.plugins.values().any(|pl| &pl.file_name == *jar))
+        .map(String::from)
+        .collect();
+    let mut missing: Vec<String> = app.plugins.values()
+        .filter(|pl| !jars.contains(&pl.file_name))
+        .map(|pl| String::from(&pl.name))
+        .collect();
+    orphans.sort();
+    missing.sort();
+    Some(SyncStatus { orphans, missing })
+}
+
+fn print_sync_status(app: &AppData, status: &SyncStatus) {
+    if status.orphans.is_empty() && status.missing.is_empty() {
+        println!("{}", "mngr.toml and the plugins directory are in sync.".green());
+        return
+    }
+    for orphan in &status.orphans {
+        println!("{} {}", "Not registered:".yellow(), orphan);
+    }
+    for name in &status.missing {
+        println!("{} {} ({})", "Missing jar:".yellow(), name, &app.plugins.get(name).unwrap().file_name);
+    }
+}
+
+fn sync_listener(app: &mut AppData) {
+    let status: Option<SyncStatus> = get_sync_status(app);
+    if status.is_none() { return };
+    let status: SyncStatus = status.unwrap();
+    print_sync_status(app, &status);
+
+    for name in &status.missing {
+        println!("\n{} '{}' ({})", "Missing jar of".yellow(), name.underline(), &app.plugins.get(name).unwrap().file_name);
+        println!(" {}, {} or Skip (Enter others)", "Download (Enter '0')".green(), "Unregister (Enter '1')".yellow());
+        match prompt_input("mngr > sync > select > ").as_str() {
+            "0" => { jar_download(app.plugins.get(name).unwrap(), None, app); },
+            "1" if forget_plugins(app, std::slice::from_ref(name)) => println!("{} {}", "Unregistered:".green(), name),
+            _ => (),
+        }
+    }
+
+    for orphan in &status.orphans {
+        println!("\n{} '{}'", "Not registered jar".yellow(), orphan.underline());
+        println!(" {}, {} or Skip (Enter others)", "Adopt (Enter '0')".green(), "Remove (Enter '1')".yellow());
+        match prompt_input("mngr > sync > select > ").as_str() {
+            "0" => adopt_orphan(app, orphan),
+            "1" if delete_plugin_jar(orphan, false) => println!("{} {}", "Removed:".green(), orphan),
+            _ => (),
+        }
+    }
+}
+
+fn adopt_orphan(app: &mut AppData, orphan: &str) {
+    println!("{}", "Enter the repository url of the jar. (e.g. 'https://github.com/Sakaki-Aruka/custom-crafter')".yellow());
+    let url: String = prompt_input("mngr > sync > adopt > ");
+    let before: Vec<String> = app.plugins.keys().map(String::from).collect();
+    if !register(app, &url) {
+        println!("{}", "Failed to adopt.".red());
+        return
+    }
+    let name: Option<String> = app.plugins.keys().find(|k| !before.contains(k)).map(String::from);
+    if name.is_none() { return };
+    let registered: &PluginData = app.plugins.get(&name.unwrap()).unwrap();
+    if registered.file_name == orphan { return };
+
+    println!("{} '{}' -> '{}'", "The registered release has a different jar.".yellow(), orphan, &registered.file_name);
+    println!(" {}, or {}", "Replace the jar (Enter '0')".green(), "Keep it (Enter other than '0')".yellow());
+    if prompt_input("mngr > sync > adopt > select > ").as_str() != "0" { return };
+    if jar_download(registered, None, app) {
+        delete_plugin_jar(orphan, false);
+    }
+}
+
+fn sync_command(app: &mut AppData, download: bool, remove_orphans: bool) -> bool {
+    let status: Option<SyncStatus> = get_sync_status(app);
+    if status.is_none() { return false };
+    let status: SyncStatus = status.unwrap();
+    print_sync_status(app, &status);
+
+    let mut in_sync: bool = true;
+    for name in &status.missing {
+        if !download || !jar_download(app.plugins.get(name).unwrap(), None, app) { in_sync = false; }
+    }
+    for orphan in &status.orphans {
+        if !remove_orphans || !delete_plugin_jar(orphan, false) { in_sync = false; }
+    }
+    in_sync
+}
+
+fn prompt_input(prompt: &str) -> String {
+    print!("{}", prompt);
+    stdout().flush().unwrap();
+    let mut input: String = String::new();
+    stdin().read_line(&mut input).ok();
+    input.trim_end().to_string()
+}
+
+
+fn update_listener(app: &mut AppData) {
+    // input types
+    // (implemented) #all -> all update to latest version
+    // (implemented) #!pre -> update to latest "stable" release
+    // (no used) #only-marked -> updates what is marked 'latest'
+    // (no used) #!only-marked -> updates what is not marked 'latest'
+    // #pls (plugin_name,plugin_name,plugin_name) -> updates what is specified
+    // #~(RFC3339 formatted date) -> updates what is published before specified date
+    // #(RFC3339 formatted date)~ -> updates what is published after specified date
+    // (implemented) #plv -> Enter select plugin and version mode.
+    //
+    loop {
+        print!("mngr > update > ");
+        stdout().flush().unwrap();
+        let mut input: String = String::new();
+        stdin().read_line(&mut input).ok();
+        let input: String = input.trim_end().to_string();
+        match input.as_str() {
+            "exit" | "E" | "e" => break,
+            "#all" => {
+                let all: Vec<String> = get_plugin_names(app);
+                all_update(&all, app);
+            },
+            "#!pre" => {
+                let without_pre: Option<Vec<String>> = get_not_prerelease_plugins_name(app);
+                if without_pre.is_none() {
+                    println!("{}", "mngr does not have any plugins that are marked as 'pre-release'.".green());
+                    continue
+                }
+                all_update(&without_pre.unwrap(), app);
+            },
+            "#multi" => {
+                multiple_plugins_update_listener(app);
+            },
+            "#plv" => {
+                select_version_listener(app);
+            },
+            "#constraint" => {
+                constraint_listener(app);
+            },
+            "" => {
+                println!("'{}' = To update all plugins that are registered.", "#all".green());
+                println!("'{}' = To update that are not marked 'pre-release'.", "#!pre".green());
+                println!("'{}' = To update that are specified plugins.", "#multi".green());
+                println!("'{}' = To select a plugin and install (and pin) the specified version.", "#plv".green());
+                println!("'{}' = To set a version constraint of a plugin. (e.g. '^4.1')", "#constraint".green());
+            },
+            _ => (),
+        }
+    }
+}
+
+fn fetch_releases(pl: &PluginData, app: &AppData, out: &Messages) -> Option<HashMap<DateTime<Utc>, PluginData>> {
+    match pl.source {
+        SourceKind::GitHub => (),
+        SourceKind::Modrinth => return fetch_modrinth_releases(&pl.project_id, pl.asset_pattern.as_deref(), app, out),
+        SourceKind::Hangar => return fetch_hangar_releases(&pl.project_id, pl.asset_pattern.as_deref(), app, out),
+        SourceKind::Spigot => return fetch_spiget_releases(&pl.project_id, app, out),
+        SourceKind::Jenkins => return fetch_jenkins_releases(&pl.project_id, pl.asset_pattern.as_deref(), app, out),
+        SourceKind::Actions => return fetch_actions_releases(&format!("{}#{}", &pl.repository_url, &pl.project_id), app, out),
+        SourceKind::Url if app.offline => {
+            out.line(format!("{} {}", "Direct links are not checked offline:".yellow(), &pl.name));
+            return None
+        },
+        SourceKind::Url => return fetch_url_releases(&pl.project_id, Some(pl), app, out),
+        SourceKind::GitLab => return fetch_gitlab_releases(&pl.repository_url, pl.asset_pattern.as_deref(), app, out),
+        SourceKind::Gitea => return fetch_gitea_releases(&pl.repository_url, pl.asset_pattern.as_deref(), app, out),
+        SourceKind::File => {
+            out.line(format!("{} {}", "Local files are not updated by mngr:".yellow(), &pl.name));
+            return None
+        },
+    }
+    let url: core::result::Result<String, String> = get_releases_url(&pl.repository_url, app);
+    if let Err(error) = &url {
+        out.line(format!("{}", error.red()));
+        return None
+    }
+    fetch_github_releases(&url.unwrap(), pl.asset_pattern.as_deref(), false, app, out)
+}
+
+fn fetch_next_pages(next: Option<String>, asset_pattern: Option<&str>, app: &AppData, releases: &mut Releases, out: &Messages) {
+    // follows 'rel="next"' until 'max_pages' (including the first page) is reached
+    let mut next: Option<String> = next;
+    let mut page: u32 = 1;
+    while let Some(url) = next {
+        if page >= app.github.max_pages {
+            out.line(format!("{} {}", "Older releases are not listed. Reached 'max_pages' in 'mngr.toml':".yellow(), app.github.max_pages));
+            break
+        }
+        // each page is one more API request
+        if !ensure_rate_limit(app, out) { break };
+        let response: Option<CachedResponse> = send_cached(get_github_request_builder(&url, app), app, out);
+        if response.as_ref().is_none_or(|r| r.status != 200) {
+            out.line(format!("{} {}", "Failed to get the next page of releases.".yellow(), url));
+            break
+        }
+        let response: CachedResponse = response.unwrap();
+        next = get_next_link(&response.link);
+        releases.extend(response_parser(&response.body, asset_pattern, out));
+        page += 1;
+    }
+}
+
+fn print_releases(releases: &HashMap<DateTime<Utc>, PluginData>, current: &PluginData) {
+    let mut dates: Vec<&DateTime<Utc>> = releases.keys().collect();
+    dates.sort();
+    for date in dates.iter().rev() {
+        let release: &PluginData = releases.get(date).unwrap();
+        let mut line: String = format!(" - {} ({}, {})", &release.version, &release.file_name, date.format("%Y-%m-%d"));
+        if release.pre_release { line.push_str(" [pre-release]"); }
+        if release.version == current.version { println!("{} {}", line.green(), "<- installed".green()); }
+        else { println!("{}", line); }
+    }
+}
+
+fn select_version_listener(app: &mut AppData) {
+    // '#plv' -> select a plugin and a version, and pins the plugin to it.
+    let name: String = prompt_input("mngr > update > plv > plugin name > ");
+    if !app.plugins.contains_key(&name) {
+        println!("{} {}", "Not registered:".red(), name);
+        return
+    }
+    let releases: Option<HashMap<DateTime<Utc>, PluginData>> = fetch_releases(app.plugins.get(&name).unwrap(), app, &Messages::printed());
+    if releases.is_none() { return };
+    let releases: HashMap<DateTime<Utc>, PluginData> = releases.unwrap();
+    if releases.is_empty() {
+        println!("{} '{}'", "No releases in".red(), name.underline());
+        return
+    }
+    print_releases(&releases, app.plugins.get(&name).unwrap());
+    let tag: String = prompt_input("mngr > update > plv > version > ");
+    if tag.is_empty() { return };
+    update_to(app, &name, &tag);
+}
+
+fn update_to(app: &mut AppData, name: &str, tag: &str) -> bool {
+    let pl: &PluginData = app.plugins.get(name).unwrap();
+    println!("\nUpdate Target = {} -> {}", &pl.name.underline(), tag);
+    let out: Messages = Messages::printed();
+    let releases: Option<HashMap<DateTime<Utc>, PluginData>> =
+        if pl.source == SourceKind::GitHub { fetch_github_release(pl, Some(tag), app, &out) }
+        else { fetch_releases(pl, app, &out) };
+    if releases.is_none() { return false };
+    let release: Option<PluginData> = releases.unwrap().into_values().find(|r| r.version == tag);
+    if release.is_none() {
+        println!("{} '{}'", "No release has the tag".red(), tag);
+        return false
+    }
+    let mut release: PluginData = release.unwrap();
+    if pl.constraint.as_ref().is_some_and(|c| !satisfies_constraint(&release.version, c)) {
+        println!("{} '{}'", "The release does not satisfy the constraint".yellow(), pl.constraint.as_ref().unwrap());
+    }
+    if let Some(reason) = get_incompatibility(&release, &app.server) {
+        println!("{} ({})", "The release may not work on this server.".yellow(), reason);
+    }
+    inherit_settings(&mut release, pl);
+    release.pinned = Some(String::from(tag));
+    if release.version == pl.version {
+        println!("{} {}", "Already installed. Pinned to".green(), tag);
+        app.plugins.get_mut(name).unwrap().pinned = Some(String::from(tag));
+        return true
+    }
+    if !jar_download(&release, Some(pl), app) {
+        println!("{}", "Failed to download the plugin jar file.".red());
+        println!("{} {}", "Kept the current jar:".yellow(), &pl.file_name);
+        return false
+    }
+    let old: Option<PluginData> = app.plugins.insert(String::from(name), release);
+    if let Some(old) = old { push_history(app, old); }
+    println!("{} {}", "Pinned to".green(), tag);
+    true
+}
+
+fn get_not_prerelease_plugins_name(app: &AppData) -> Option<Vec<String>> {
+    if app.plugins.is_empty() { return None };
+    let mut result:  Vec<String> = Vec::new();
+    for pl in app.plugins.values() {
+        if !pl.pre_release { result.push(String::from(&pl.name)); }
+    }
+    result.sort();
+    Some(result)
+}
+
+fn get_plugin_names(app: &AppData) -> Vec<String> {
+    // sorted, so batch updates are applied in the same order every time
+    let mut names: Vec<String> = app.plugins.keys().map(String::from).collect();
+    names.sort();
+    names
+}
+
+fn multiple_plugins_update_listener(app: &mut AppData) {
+    let mut candidate: Vec<String> = Vec::new();
+    loop {
+        print!("mngr > update > multi > ");
+        stdout().flush().unwrap();
+        let mut input: String = String::new();
+        stdin().read_line(&mut input).ok();
+        let input: String = input.trim_end().to_string();
+        if input.is_empty() {
+            println!("{}", "Enter plugins name those separated with ','.".yellow());
+            continue
+        }
+        for p in input.split(',').map(String::from) {
+            candidate.push(p.replace(" ", ""));
+        }
+        break
+    }
+
+    let mut remove_candidate: Vec<String> = Vec::new();
+    for name in candidate {
+        if !app.plugins.contains_key(&name) {
+            println!("{} {}", "Not registered:".red(), name);
+            continue
+        }
+        remove_candidate.push(name);
+    }
+    if !remove_candidate.is_empty() {
+        all_update(&remove_candidate, app);
+    }
+}
+
+fn remove_pre_release(data: &mut HashMap<DateTime<Utc>, PluginData>) {
+    let mut remove_candidate: Vec<DateTime<Utc>> = Vec::new();
+    for content in data.iter().clone() {
+        if content.1.pre_release { remove_candidate.push(*content.0); }
+    }
+    if remove_candidate.is_empty() { return };
+    for key in remove_candidate {
+        data.remove(&key);
     }
 }
 
@@ -465,77 +2316,340 @@ fn get_latest_plugin(data: &mut HashMap<DateTime<Utc>, PluginData>) -> Option<Pl
     data.remove(latest_date)
 }
 
-fn get_releases_request_builder(pl: &PluginData, app: &AppData) -> RequestBuilder {
-    let url_parsed: Vec<String> = String::from(&pl.repository_url).split("/").map(|c| String::from(c)).collect();
-    let request_url: String = String::from(format!("https://api.github.com/repos/{}/{}/releases", &url_parsed[3], &url_parsed[4]));
-    let mut builder: RequestBuilder = blocking::Client::new().get(&request_url);
+fn normalize_version(version: &str) -> String {
+    // 'v4.1' -> '4.1.0', so that tags and requirements are compared as semver
+    let version: &str = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    let split: usize = version.find(['-', '+']).unwrap_or(version.len());
+    let (core, rest) = version.split_at(split);
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.len() >= 3 || parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return String::from(version)
+    }
+    let mut normalized: String = String::from(core);
+    for _ in parts.len()..3 { normalized.push_str(".0"); }
+    normalized.push_str(rest);
+    normalized
+}
+
+fn parse_constraint(constraint: &str) -> Option<Requirement> {
+    let constraint: &str = constraint.trim();
+    let split: usize = constraint.find(|c: char| !"=<>~^*".contains(c)).unwrap_or(constraint.len());
+    let (op, version) = constraint.split_at(split);
+    if op.is_empty() { return None };
+    if op == "*" { return Requirement::new(op) };
+    Requirement::new(format!("{}{}", op, normalize_version(version.trim())).as_str())
+}
+
+fn satisfies_constraint(tag: &str, constraint: &str) -> bool {
+    let requirement: Option<Requirement> = parse_constraint(constraint);
+    let version: Option<Versioning> = Versioning::new(normalize_version(tag).as_str());
+    requirement.is_some_and(|r| version.is_some_and(|v| r.matches(&v)))
+}
+
+fn is_older_version(version: &str, installed: &str) -> bool {
+    // false when one of them is not a version (e.g. a build number what 'Versioning' can not compare)
+    let version: Option<Versioning> = Versioning::new(normalize_version(version).as_str());
+    let installed: Option<Versioning> = Versioning::new(normalize_version(installed).as_str());
+    version.is_some_and(|v| installed.is_some_and(|i| v < i))
+}
+
+fn get_constrained_plugin(data: &mut HashMap<DateTime<Utc>, PluginData>, constraint: &str, out: &Messages) -> Option<PluginData> {
+    // the highest version what matches the constraint (not the newest one)
+    let requirement: Option<Requirement> = parse_constraint(constraint);
+    if requirement.is_none() {
+        out.line(format!("{} '{}'", "Failed to parse the constraint.".red(), constraint));
+        return None
+    }
+    let requirement: Requirement = requirement.unwrap();
+    let key: DateTime<Utc> = data.iter()
+        .filter_map(|(date, pl)| Versioning::new(normalize_version(&pl.version).as_str()).map(|v| (v, *date)))
+        .filter(|(version, _)| requirement.matches(version))
+        .max_by(|a, b| a.0.cmp(&b.0))?
+        .1;
+    data.remove(&key)
+}
+
+fn set_constraint(app: &mut AppData, name: &str, constraint: Option<&str>) -> bool {
+    if !app.plugins.contains_key(name) {
+        println!("{} {}", "Not registered:".red(), name);
+        return false
+    }
+    if let Some(constraint) = constraint {
+        if parse_constraint(constraint).is_none() {
+            println!("{} '{}'", "Failed to parse the constraint.".red(), constraint);
+            println!("{}", "It needs an operator. (e.g. '^4.1', '~2.3.0', '<5', '>=1.2.0' or '*')".yellow());
+            return false
+        }
+    }
+    app.plugins.get_mut(name).unwrap().constraint = constraint.map(String::from);
+    match constraint {
+        Some(constraint) => println!("{} {} -> {}", "The constraint has been set.".green(), name, constraint),
+        None => println!("{} {}", "The constraint has been cleared.".green(), name),
+    }
+    true
+}
+
+fn constraint_listener(app: &mut AppData) {
+    let name: String = prompt_input("mngr > update > constraint > plugin name > ");
+    if let Some(current) = app.plugins.get(&name).and_then(|pl| pl.constraint.as_ref()) {
+        println!("Current = {}", current.green());
+    }
+    println!("{}", "Enter a constraint (e.g. '^4.1', '~2.3.0', '<5'), or enter nothing to clear it.".yellow());
+    let constraint: String = prompt_input("mngr > update > constraint > ");
+    set_constraint(app, &name, if constraint.is_empty() { None } else { Some(constraint.as_str()) });
+}
+
+fn get_releases_url(repository_url: &str, app: &AppData) -> core::result::Result<String, String> {
+    let (author, repository_name) = get_github_repository(repository_url, app)?;
+    Ok(format!("{}/repos/{}/{}/releases", app.github.api_url.trim_end_matches('/'), &author, &repository_name))
+}
+
+fn get_github_request_builder(url: &str, app: &AppData) -> RequestBuilder {
+    let mut builder: RequestBuilder = app.api.client.get(url);
     if !&app.github_token.is_empty() { builder = builder.header("Authorization", format!("token {}", &app.github_token)); };
     builder = builder.header("X-GitHub-Api-Version", "2022-11-28");
     builder = builder.header("User-Agent", "mngr");
     builder = builder.header("Accept", "application/vnd.github.v3+json");
     builder
 }
-fn all_update(data: &Vec<String>, app: &mut AppData) {
-    let mut new: Vec<PluginData> = Vec::new();
-    for name in data {
-        let pl: &PluginData = app.plugins.get(name).unwrap();
-        println!("\nUpdate Target = {}", &pl.name.underline());
-        let builder: RequestBuilder = get_releases_request_builder(pl, app);
-        let response: reqwest::Result<Response> = builder.send();
-        if response.is_err() {
-            println!("{}", "Failed to get plugin data from GitHub API.".red());
-            continue
+
+fn get_github_repository(url: &str, app: &AppData) -> core::result::Result<(String, String), String> {
+    // (web_url)/(UserName)/(RepositoryName)(.git) -> ((UserName), (RepositoryName))
+    // the error tells the 'web_url' when the repository is not under it (e.g. 'web_url' was changed after the registration)
+    let web_url: &str = app.github.web_url.trim_end_matches('/');
+    let path: Option<&str> = url.strip_prefix(web_url).filter(|p| p.starts_with('/'));
+    if path.is_none() {
+        return Err(format!("'{}' is not a repository on 'web_url' ({}) in 'mngr.toml'.", url, web_url))
+    }
+    let mut parsed = path.unwrap().trim_start_matches('/').split('/');
+    let author: Option<&str> = parsed.next().filter(|a| !a.is_empty());
+    let repository_name: Option<&str> = parsed.next().filter(|r| !r.is_empty());
+    if author.is_none() || repository_name.is_none() {
+        return Err(format!("Failed to parse the repository url. {}", url))
+    }
+    let repository_name: &str = repository_name.unwrap();
+    Ok((String::from(author.unwrap()), String::from(repository_name.strip_suffix(".git").unwrap_or(repository_name))))
+}
+fn get_update_target(pl: &PluginData, app: &AppData, out: &Messages) -> Option<PluginData> {
+    // the release what 'update' installs. (not a pre-release, compatible with the server, and satisfies the constraint)
+    let plugins: Option<HashMap<DateTime<Utc>, PluginData>> =
+        if pl.source == SourceKind::GitHub && pl.constraint.is_none() { fetch_github_release(pl, None, app, out) }
+        else { fetch_releases(pl, app, out) };
+    let mut plugins: HashMap<DateTime<Utc>, PluginData> = plugins?;
+    remove_pre_release(&mut plugins);
+    remove_incompatible(&mut plugins, app, out);
+    if plugins.is_empty() {
+        out.line(format!("{} '{}'", "No releases in".red(), &pl.name.underline()));
+        return None
+    }
+    let plugin: Option<PluginData> = match &pl.constraint {
+        Some(constraint) => get_constrained_plugin(&mut plugins, constraint, out),
+        None => get_latest_plugin(&mut plugins),
+    };
+    if plugin.is_none() {
+        out.line(format!("{} '{}' {}", "No releases in".red(), &pl.name.underline(), format!("match '{}'", pl.constraint.as_ref().unwrap()).red()));
+    }
+    plugin
+}
+
+fn outdated(app: &AppData) -> bool {
+    // compares the installed versions with the ones what 'update' would install
+    let mut names: Vec<&String> = app.plugins.iter()
+        .filter(|(_, pl)| pl.source != SourceKind::File)
+        .map(|(name, _)| name)
+        .collect();
+    names.sort();
+    let stopped: AtomicBool = AtomicBool::new(false);
+    let checked: Vec<Captured<Option<PluginData>>> = parallel_map(&names, app, |name, out| {
+        if stopped.load(Ordering::SeqCst) || !ensure_rate_limit(app, out) {
+            stopped.store(true, Ordering::SeqCst);
+            return None
         }
-        let response: Response = response.unwrap();
-        let mut plugins: HashMap<DateTime<Utc>, PluginData> = response_parser(response);
-        remove_pre_release(&mut plugins);
-        if plugins.is_empty() {
-            println!("{} '{}'", "No releases in".red(), &pl.name.underline());
+        get_update_target(app.plugins.get(*name).unwrap(), app, out)
+    });
+    let mut failed: bool = false;
+    let mut outdated: usize = 0;
+    for (name, (latest, messages)) in names.into_iter().zip(checked) {
+        let pl: &PluginData = app.plugins.get(name).unwrap();
+        messages.iter().for_each(|line| println!("{}", line));
+        if latest.is_none() {
+            println!("{} {}", "Failed to check:".red(), name);
+            failed = true;
             continue
         }
-        let plugin: PluginData = get_latest_plugin(&mut plugins).unwrap();
-        if !delete_plugin_jar(&pl.file_name, false) {
-            println!("{}", "Failed to remove the plugin file.".red());
-            println!("{} {}\n", "Continued to update".green(), &pl.name.underline());
+        let latest: PluginData = latest.unwrap();
+        if latest.version == pl.version || is_older_version(&latest.version, &pl.version) { continue };
+        outdated += 1;
+        match &pl.pinned {
+            Some(pinned) => println!("{}: {} -> {} {}", name, &pl.version, latest.version.green(), format!("(pinned to {})", pinned).yellow()),
+            None => println!("{}: {} -> {}", name, &pl.version, latest.version.green()),
         }
-        if !jar_download(&plugin) {
-            println!("{}", "Failed to download the plugin jar file.".red());
-            continue
+    }
+    if outdated > 0 { println!("{} {}", outdated, "plugins can be updated.".yellow()) }
+    else if !failed { println!("{}", "All plugins are up to date.".green()) };
+    !failed
+}
+
+fn all_update(data: &[String], app: &mut AppData) -> bool {
+    // 1. releases are checked and jars are downloaded by 'workers' threads
+    // 2. the jars and the entries of mngr.toml are replaced one by one in the given order
+    let stopped: AtomicBool = AtomicBool::new(false);
+    let shared: &AppData = app;
+    let outcomes: Vec<Captured<UpdateOutcome>> = parallel_map(data, shared, |name, out| check_update(shared.plugins.get(name).unwrap(), shared, &stopped, out));
+
+    let mut updated: Vec<String> = Vec::new();
+    let mut up_to_date: usize = 0;
+    let mut skipped: usize = 0;
+    let mut failed: Vec<String> = Vec::new();
+    for (name, (outcome, messages)) in data.iter().zip(outcomes) {
+        println!("\nUpdate Target = {}", name.underline());
+        // the messages of the worker thread what checked this plugin
+        messages.iter().for_each(|line| println!("{}", line));
+        match outcome {
+            UpdateOutcome::Skipped(reason) => {
+                println!("{}", reason.yellow());
+                skipped += 1;
+            },
+            UpdateOutcome::UpToDate(version) => {
+                println!("{} {}", "Already up to date:".green(), version);
+                up_to_date += 1;
+            },
+            UpdateOutcome::Failed(reason) => {
+                println!("{}", reason.red());
+                failed.push(String::from(name));
+            },
+            UpdateOutcome::Downloaded(plugin, download_url, staged) => {
+                let pl: &PluginData = app.plugins.get(name).unwrap();
+                // the old jar is kept until the staged one replaces it
+                if !install_jar(&plugin, Some(pl), &download_url, &staged) {
+                    println!("{} {}", "Kept the current jar:".yellow(), &pl.file_name);
+                    failed.push(String::from(name));
+                    continue
+                }
+                updated.push(format!("{} ({} -> {})", name, &pl.version, &plugin.version));
+                let old: Option<PluginData> = app.plugins.insert(String::from(name), *plugin);
+                if let Some(old) = old { push_history(app, old); }
+            },
         }
-        new.push(plugin);
     }
 
-    if new.is_empty() { return };
-    for plugin in new {
-        app.plugins.remove(&plugin.name);
-        app.plugins.insert(String::from(&plugin.name), plugin);
+    println!("\n{} updated: {}, up to date: {}, skipped: {}, failed: {}", "Result =".underline(), updated.len(), up_to_date, skipped, failed.len());
+    for line in &updated {
+        println!("  {} {}", "updated".green(), line);
+    }
+    for name in &failed {
+        println!("  {} {}", "failed".red(), name);
+    }
+    failed.is_empty()
+}
+
+fn check_update(pl: &PluginData, app: &AppData, stopped: &AtomicBool, out: &Messages) -> UpdateOutcome {
+    // runs on a worker thread. nothing in 'plugins' or mngr.toml is changed here.
+    if let Some(pinned) = &pl.pinned { return UpdateOutcome::Skipped(format!("Pinned to {} (skipped)", pinned)) };
+    if pl.source == SourceKind::File { return UpdateOutcome::Skipped(String::from("Local file (skipped)")) };
+    if stopped.load(Ordering::SeqCst) || !ensure_rate_limit(app, out) {
+        stopped.store(true, Ordering::SeqCst);
+        return UpdateOutcome::Failed(String::from("Not checked to keep the API rate limit."))
+    }
+    let plugin: Option<PluginData> = get_update_target(pl, app, out);
+    if plugin.is_none() { return UpdateOutcome::Failed(String::from("Failed to check the releases.")) };
+    let mut plugin: PluginData = plugin.unwrap();
+    if plugin.version == pl.version { return UpdateOutcome::UpToDate(String::from(&pl.version)) };
+    if is_older_version(&plugin.version, &pl.version) {
+        // e.g. a constraint what matches only older releases ('update (plugin name) --to (tag)' installs them)
+        return UpdateOutcome::Skipped(format!("{} is older than the installed {} (skipped)", &plugin.version, &pl.version))
     }
+    inherit_settings(&mut plugin, pl);
+    match prepare_jar(&plugin, Some(pl), app, out) {
+        Some((download_url, staged)) => UpdateOutcome::Downloaded(Box::new(plugin), download_url, staged),
+        None => UpdateOutcome::Failed(format!("Failed to download the plugin jar file. ({})", &plugin.version)),
+    }
+}
+
+fn parallel_map<T: Sync, U: Send>(items: &[T], app: &AppData, task: impl Fn(&T, &Messages) -> U + Sync) -> Vec<Captured<U>> {
+    // runs 'task' on at most 'workers' threads. the results are in the same order as 'items'.
+    // the messages of 'task' are kept and returned with its result
+    let workers: usize = app.workers.clamp(1, items.len().max(1));
+    let next: AtomicUsize = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<Captured<U>>>> = Mutex::new(items.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let index: usize = next.fetch_add(1, Ordering::SeqCst);
+                if index >= items.len() { break };
+                let out: Messages = Messages::kept();
+                let result: U = task(&items[index], &out);
+                results.lock().unwrap()[index] = Some((result, out.into_lines()));
+            });
+        }
+    });
+    results.into_inner().unwrap().into_iter().map(|r| r.unwrap()).collect()
 }
 
+fn jar_download(plugin: &PluginData, previous: Option<&PluginData>, app: &AppData) -> bool {
+    // previous: the installed version that is replaced by this download (moved into the backup area)
+    let prepared: Option<(String, PathBuf)> = prepare_jar(plugin, previous, app, &Messages::printed());
+    if prepared.is_none() { return false };
+    let (download_url, staged) = prepared.unwrap();
+    install_jar(plugin, previous, &download_url, &staged)
+}
 
-fn jar_download(plugin: &PluginData) -> bool {
+fn prepare_jar(plugin: &PluginData, previous: Option<&PluginData>, app: &AppData, out: &Messages) -> Option<(String, PathBuf)> {
+    // downloads and verifies the jar, and stages it into a temporary file in 'plugins' (safe on worker threads)
     // https://github.com/Sakaki-Aruka/custom-crafter/releases/tag/v4.1.6
     // https://github.com/Sakaki-Aruka/custom-crafter/releases/download/v4.1.6/custom-crafter-4.1.6.jar
     // -> (repository-url)/releases/download/(version)/(file name)
-    let download_url: String = String::from(format!("{}/releases/download/{}/{}", &plugin.repository_url.as_str(), &plugin.version, &plugin.file_name)); // fix here
-    let mut builder: RequestBuilder = blocking::Client::new().get(&download_url);
-    builder = builder.header("User-Agent", "mngr");
-    let response: reqwest::Result<Response> = builder.send();
-    if response.is_err() {
-        println!("{} From: {}", "Failed to download a release file.".red(), &download_url.underline());
-        return false
+    let download_url: Option<String> = get_download_url(plugin);
+    if download_url.is_none() && plugin.source == SourceKind::Spigot {
+        out.line(format!("{} {}", "Only the latest version of a Spigot resource can be installed:".red(), &plugin.version));
+        return None
     }
-    let response: Response = response.unwrap();
+    if download_url.is_none() {
+        out.line(format!("{} {}", "No download url is known for".red(), &plugin.file_name));
+        return None
+    }
+    let download_url: String = download_url.unwrap();
+    let content: Vec<u8> = download_bytes(&download_url, app, out)?;
+    if !verify_checksum(plugin, &content, app, out) {
+        out.line(format!("{} {}", "Refused to install".red(), &plugin.file_name.underline()));
+        return None
+    }
+    let content: Vec<u8> =
+        if plugin.source != SourceKind::Actions { content }
+        else { extract_jar(&content, plugin.asset_pattern.as_deref(), out)? };
+    let incompatibility: Option<String> = ZipArchive::new(Cursor::new(content.as_slice())).ok()
+        .and_then(|mut archive| get_jar_incompatibility(&mut archive, &plugin.file_name, &app.server, out));
+    if let Some(reason) = incompatibility {
+        // automatic updates keep the working jar, explicitly chosen releases are installed with a warning
+        if previous.is_some() && plugin.pinned.is_none() {
+            out.line(format!("{} {} ({})", "Skipped the incompatible release:".yellow(), &plugin.version, reason));
+            return None
+        }
+        out.line(format!("{} ({})", "The jar may not work on this server.".yellow(), reason));
+    }
+    let staged: PathBuf = stage_jar(&plugin.file_name, &content, out)?;
+    Some((download_url, staged))
+}
 
+fn get_download_url(plugin: &PluginData) -> Option<String> {
+    // (GitHub) (repository-url)/releases/download/(version)/(file name)
+    if !plugin.download_url.is_empty() { return Some(String::from(&plugin.download_url)) };
+    if plugin.source != SourceKind::GitHub { return None };
+    Some(format!("{}/releases/download/{}/{}", &plugin.repository_url, &plugin.version, &plugin.file_name))
+}
+
+fn install_jar(plugin: &PluginData, previous: Option<&PluginData>, download_url: &str, staged: &Path) -> bool {
+    // staged: the verified jar from 'prepare_jar' (removed when it is not installed)
     let filename: String = String::from(&plugin.file_name);
     let path: Option<PathBuf> = get_plugins_directory_path();
     if path.is_none() {
         println!("{}", "Failed to handle 'plugins' directory's path.".red());
+        fs::remove_file(staged).ok();
         return false;
     }
     let mut path: PathBuf = path.unwrap();
     path.push(filename);
-    if path.exists() {
+    if path.exists() && previous.is_none() {
         println!("{}", "The file has already exists. What do you want to do to it?".yellow());
         println!("Target file: {}", &plugin.file_name.underline());
         println!(" {}, or {}", "Delete and Update (Enter '0')".green(), "Not change (Enter other than '0')".yellow());
@@ -545,114 +2659,341 @@ fn jar_download(plugin: &PluginData) -> bool {
         stdin().read_line(&mut select).ok();
         let select: String = select.trim_end().to_string();
         match select.as_str() {
-            "0" => {
-                if fs::remove_file(&path.as_path()).is_err() {
-                    println!("{}", "Failed to remove the file.".red());
-                    return false
-                }
-                if fs::File::create(&path.as_path()).is_err() {
-                    println!("{}", "Failed to create the file.".red());
-                    return false
-                }
-            },
+            "0" => (),
             _ => {
                 println!("{} {}.", "Cancel to install".yellow(), &plugin.file_name.yellow());
+                fs::remove_file(staged).ok();
+                return false
+            }
+        }
+    }
+    if !replace_jar(staged, &plugin.file_name, previous) {
+        println!("{}", "Failed to save the downloaded content.".red());
+        return false
+    }
+    println!("{}", "The plugin has been successfully download.".green());
+    println!("Saved as '{}'", &path.to_str().unwrap());
+    if let Ok(content) = fs::read(path.as_path()) { lock_plugin(plugin, download_url, &content); }
+    true
+}
+
+fn place_jar(file_name: &str, content: &[u8], previous: Option<&PluginData>) -> bool {
+    // when a step fails, the previous jar is left as it is.
+    let staged: Option<PathBuf> = stage_jar(file_name, content, &Messages::printed());
+    if staged.is_none() { return false };
+    replace_jar(&staged.unwrap(), file_name, previous)
+}
+
+fn stage_jar(file_name: &str, content: &[u8], out: &Messages) -> Option<PathBuf> {
+    // writes the content into a temporary file in 'plugins' (same file system, so the rename in 'replace_jar' is atomic)
+    // and validates it. returns the staged path.
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    if directory.is_none() || fs::create_dir_all(directory.as_ref().unwrap()).is_err() {
+        out.line(format!("{}", "Failed to handle 'plugins' directory's path.".red()));
+        return None
+    }
+    let mut staged: PathBuf = directory.unwrap();
+    // unique, because the workers stage jars at the same time (e.g. the same file name in two plugins)
+    staged.push(format!(".{}.{}.mngr-tmp", file_name, Uuid::new_v4()));
+
+    if fs::write(staged.as_path(), content).is_err() {
+        out.line(format!("{}", "Failed to stage the downloaded content.".red()));
+        fs::remove_file(staged.as_path()).ok();
+        return None
+    }
+    if !validate_jar(&staged, content.len()) {
+        out.line(format!("{} {}", "The downloaded file is not a valid jar.".red(), file_name));
+        fs::remove_file(staged.as_path()).ok();
+        return None
+    }
+    Some(staged)
+}
+
+fn replace_jar(staged: &Path, file_name: &str, previous: Option<&PluginData>) -> bool {
+    // 1. hard-link (or copy) the previous jar into the backup area
+    // 2. rename the staged file over the target, so 'plugins' always has the jar
+    // 3. remove the previous jar when its file name is not the same as the new one
+    // when a step fails, the staged file is removed and the previous jar is left as it is.
+    let mut target: PathBuf = staged.to_path_buf();
+    target.set_file_name(file_name);
+
+    let backup: Option<PathBuf> = match previous {
+        Some(pl) => match backup_jar(pl) {
+            Ok(backup) => backup,
+            Err(_) => {
+                println!("{} {}", "Failed to back up the current jar.".red(), &pl.file_name);
+                fs::remove_file(staged).ok();
+                return false
+            }
+        },
+        None => None,
+    };
+
+    if fs::rename(staged, target.as_path()).is_err() {
+        println!("{}", "Failed to replace the jar. The previous one is kept.".red());
+        fs::remove_file(staged).ok();
+        if let Some(backup) = backup { fs::remove_file(backup).ok(); }
+        return false
+    }
+    if let Some(pl) = previous.filter(|pl| pl.file_name != file_name) {
+        let mut old: PathBuf = target.clone();
+        old.set_file_name(&pl.file_name);
+        if old.exists() && fs::remove_file(old.as_path()).is_err() {
+            println!("{} {}", "Failed to remove the previous jar.".yellow(), &pl.file_name);
+        }
+    }
+    true
+}
+
+fn validate_jar(path: &Path, expected_size: usize) -> bool {
+    // a jar is a zip archive, so it starts with the local file header signature.
+    match fs::read(path) {
+        Ok(written) => written.len() == expected_size && written.starts_with(b"PK\x03\x04"),
+        Err(_) => false,
+    }
+}
+
+fn get_backups_directory_path() -> Option<PathBuf> {
+    let mut current: PathBuf = env::current_dir().ok()?;
+    current.push(".mngr");
+    current.push("backups");
+    Some(current)
+}
+
+fn get_backup_path(plugin: &PluginData) -> Option<PathBuf> {
+    // .mngr/backups/(plugin name)/(version)/(file name)
+    let mut path: PathBuf = get_backups_directory_path()?;
+    path.push(plugin.name.replace(['/', '\\'], "_"));
+    path.push(plugin.version.replace(['/', '\\'], "_"));
+    path.push(&plugin.file_name);
+    Some(path)
+}
+
+fn backup_jar(plugin: &PluginData) -> Result<Option<PathBuf>> {
+    // returns the backup path when the jar was backed up. the jar itself is left in 'plugins'.
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    let backup: Option<PathBuf> = get_backup_path(plugin);
+    if directory.is_none() || backup.is_none() {
+        return Err(std::io::Error::new(std::io::ErrorKind::NotFound, "failed to get the directory path"))
+    }
+    let mut original: PathBuf = directory.unwrap();
+    original.push(&plugin.file_name);
+    if !original.exists() { return Ok(None) };
+    let backup: PathBuf = backup.unwrap();
+    fs::create_dir_all(backup.parent().unwrap())?;
+    if backup.exists() { fs::remove_file(backup.as_path())?; }
+    // 'hard_link' fails across file systems, so falls back to copy.
+    if fs::hard_link(&original, &backup).is_err() { fs::copy(&original, &backup)?; }
+    println!("{} {}", "Backed up:".green(), backup.to_str().unwrap());
+    Ok(Some(backup))
+}
+
+fn verify_checksum(plugin: &PluginData, content: &[u8], app: &AppData, out: &Messages) -> bool {
+    // priority: pinned in mngr.toml -> GitHub asset digest -> '.sha256' / '.sha512' asset
+    let expected: String =
+        if let Some(pinned) = &plugin.checksum { String::from(pinned) }
+        else if !plugin.digest.is_empty() { String::from(&plugin.digest) }
+        else if !plugin.checksum_url.is_empty() {
+            let published: Option<Vec<u8>> = download_bytes(&plugin.checksum_url, app, out);
+            if published.is_none() {
+                out.line(format!("{}", "Failed to get the published checksum.".red()));
                 return false
             }
+            // e.g. '(hex)  (file name)' or '(hex)'
+            let published: String = String::from_utf8_lossy(&published.unwrap()).to_string();
+            let hash: &str = published.split_whitespace().next().unwrap_or("");
+            let algorithm: &str = if plugin.checksum_url.ends_with(".sha512") { "sha512" } else { "sha256" };
+            format!("{}:{}", algorithm, hash)
+        } else {
+            out.line(format!("{} {}", "No checksum is published for".yellow(), &plugin.file_name));
+            return true
+        };
+
+    let (algorithm, hash) = expected.split_once(':').unwrap_or(("sha256", expected.as_str()));
+    let actual: String = match algorithm.to_lowercase().as_str() {
+        "sha256" => sha256_hex(content),
+        "sha512" => format!("{:x}", Sha512::digest(content)),
+        _ => {
+            out.line(format!("{} '{}'", "Unsupported checksum algorithm.".red(), algorithm));
+            return false
         }
+    };
+    if actual != hash.to_lowercase() {
+        out.line(format!("{} {}", "Checksum mismatch.".red(), &plugin.file_name));
+        out.line(format!("{} {}:{}", "Expected:".yellow(), algorithm, hash));
+        out.line(format!("{} {}:{}", "Actual:".yellow(), algorithm, actual));
+        return false
     }
-    let content = response.bytes().unwrap();
-    if fs::write(&path.as_path(), content).is_err() {
-        println!("{}", "Failed to save the downloaded content.".red());
-        false
-    } else {
-        println!("{}", "The plugin has been successfully download.".green());
-        println!("{} '{}'", "Saved as", &path.to_str().unwrap());
-        true
+    out.line(format!("{} {}:{}", "Verified:".green(), algorithm, actual));
+    true
+}
+
+fn inherit_settings(new: &mut PluginData, old: &PluginData) {
+    // user settings what are written in mngr.toml are kept through updates
+    // (a pinned checksum belongs to one jar, so it is dropped when the version changes)
+    new.checksum = if new.version == old.version { old.checksum.clone() } else { None };
+    new.pinned = old.pinned.clone();
+    new.constraint = old.constraint.clone();
+    new.asset_pattern = old.asset_pattern.clone();
+}
+
+fn push_history(app: &mut AppData, old: PluginData) {
+    let history: &mut Vec<PluginData> = app.history.entry(String::from(&old.name)).or_default();
+    history.push(old);
+    // the oldest versions over 'max_history' are forgotten with their backups
+    let over: usize = history.len().saturating_sub(app.max_history);
+    let expired: Vec<PluginData> = history.drain(..over).collect();
+    for pl in expired {
+        // the same version can be in the history twice after 'rollback'
+        if history.iter().any(|h| h.version == pl.version && h.file_name == pl.file_name) { continue };
+        let backup: Option<PathBuf> = get_backup_path(&pl);
+        if backup.is_none() { continue };
+        let backup: PathBuf = backup.unwrap();
+        fs::remove_file(backup.as_path()).ok();
+        // the version directory and the plugin directory are removed when they become empty
+        let version_directory: &Path = backup.parent().unwrap();
+        fs::remove_dir(version_directory).ok();
+        if let Some(plugin_directory) = version_directory.parent() { fs::remove_dir(plugin_directory).ok(); }
     }
 }
 
-fn get_rate_limit_remaining(response: &Response) -> Option<i16> {
-    let key: HeaderName = HeaderName::from_str("X-RateLimit-Remaining").unwrap();
-    let result: Option<&HeaderValue> = response.headers().get(key);
-    if result.is_none() { return None };
-    let header_value: &HeaderValue = result.unwrap();
-    if header_value.to_str().is_err() { return None };
-    if header_value.to_str().unwrap().parse::<i16>().is_ok() {
-        Some(header_value.to_str().unwrap().parse().unwrap())
-    } else {
-        None
+fn rollback_listener(app: &mut AppData) {
+    let name: String = prompt_input("mngr > rollback > plugin name > ");
+    if !app.plugins.contains_key(&name) {
+        println!("{} {}", "Not registered:".red(), name);
+        return
+    }
+    let history: Option<&Vec<PluginData>> = app.history.get(&name).filter(|h| !h.is_empty());
+    if history.is_none() {
+        println!("{} {}", "No previous versions of".yellow(), name.underline());
+        return
+    }
+    println!("Current = {}", &app.plugins.get(&name).unwrap().version.green());
+    for pl in history.unwrap().iter().rev() {
+        println!(" - {} ({})", &pl.version, &pl.file_name);
     }
+    println!("{}", "Enter a version, or enter nothing to restore the previous one.".yellow());
+    let version: String = prompt_input("mngr > rollback > version > ");
+    rollback(app, &name, if version.is_empty() { None } else { Some(version.as_str()) });
 }
 
-fn rate_limit_command(app: &AppData) {
-    let url: String = String::from("https://api.github.com/meta");
-    let mut builder: RequestBuilder  = blocking::Client::new().get(&url);
-    if !app.github_token.is_empty() { builder = builder.header("Authorization", format!("token {}", &app.github_token)); }
-    builder = builder.header("X-GitHub-Api-Version", "2022-11-28");
-    builder = builder.header("User-Agent", "mngr");
-    builder = builder.header("Accept", "application/vnd.github.v3+json");
-    let response: reqwest::Result<Response> = builder.send();
+fn rollback(app: &mut AppData, name: &str, version: Option<&str>) -> bool {
+    if !app.plugins.contains_key(name) {
+        println!("{} {}", "Not registered:".red(), name);
+        return false
+    }
+    let history: Vec<PluginData> = app.history.get(name).cloned().unwrap_or_default();
+    let index: Option<usize> = match version {
+        Some(version) => history.iter().rposition(|pl| pl.version == version),
+        None => if history.is_empty() { None } else { Some(history.len() - 1) },
+    };
+    if index.is_none() {
+        println!("{} {} {}", "No backed up version of".red(), name.underline(), version.unwrap_or(""));
+        return false
+    }
+    let index: usize = index.unwrap();
+    let mut restored: PluginData = history[index].clone();
+    // pinned, so the next 'update --all' does not install the version what was rolled back from
+    restored.pinned = Some(String::from(&restored.version));
+
+    let backup: Option<PathBuf> = get_backup_path(&restored);
+    let content: Option<Vec<u8>> = backup.as_ref().and_then(|path| fs::read(path.as_path()).ok());
+    if content.is_none() {
+        println!("{} {}", "The backed up jar is missing.".red(), backup.map(|p| p.to_str().unwrap().to_string()).unwrap_or_default());
+        return false
+    }
+    let content: Vec<u8> = content.unwrap();
+    let current: PluginData = app.plugins.get(name).unwrap().clone();
+    // the current jar is backed up too, so it can be restored by 'rollback (plugin name) (version)' again
+    if !place_jar(&restored.file_name, &content, Some(&current)) {
+        println!("{}", "Failed to restore the jar.".red());
+        return false
+    }
+    fs::remove_file(get_backup_path(&restored).unwrap()).ok();
+    lock_plugin(&restored, &restored.download_url, &content);
+
+    app.history.get_mut(name).unwrap().remove(index);
+    push_history(app, current);
+    println!("{} {} -> {}", "The plugin has been successfully rolled back.".green(), name, &restored.version);
+    println!("{} {} {}", "Pinned to".green(), &restored.version, format!("(run 'mngr update {} --unpin' to resume updates)", name).yellow());
+    app.plugins.insert(String::from(name), restored);
+    true
+}
+
+fn rate_limit_command(app: &AppData) -> bool {
+    let url: String = format!("{}/meta", app.github.api_url.trim_end_matches('/'));
+    let builder: RequestBuilder = get_github_request_builder(&url, app);
+    let response: core::result::Result<Response, String> = send_request(builder, app, &Messages::printed());
     if response.is_err() {
         println!("{}", "Failed to get API rate limit remaining.".red());
-        return
+        return false
     }
-    let response: Response = response.unwrap();
-    let result: Option<i16> = get_rate_limit_remaining(&response);
-    if result.is_none() {
+    // the headers are recorded in 'app.api.usage' by 'send_request'
+    let usage: ApiUsage = *app.api.usage.lock().unwrap();
+    if usage.remaining.is_none() {
         println!("{}", "Failed to get API rate limit remaining.".red());
-        return
+        return false
     }
-    println!("API rate limit remaining = {}", result.unwrap());
-    let unix_time_key: HeaderName = HeaderName::from_str("X-RateLimit-Reset").unwrap();
-    let result: Option<&HeaderValue> = (&response).headers().get(unix_time_key);
-    if result.is_none() {
-        return
+    println!("API rate limit remaining = {}", usage.remaining.unwrap());
+    if usage.reset.is_none() {
+        return true
     }
-    let result: i64 = result.unwrap().to_str().unwrap().parse::<i64>().unwrap();
+    let result: i64 = usage.reset.unwrap();
     let reset_date: DateTime<Utc> = DateTime::from_timestamp(result, 0).unwrap();
-    println!("The time when the API rate limit is lifted. (UTC) = {}", reset_date.to_utc().to_string());
+    println!("The time when the API rate limit is lifted. (UTC) = {}", reset_date.to_utc());
+    true
 }
 
 
-fn response_parser (response: Response) -> HashMap<DateTime<Utc>, PluginData> {
+fn response_parser (response_str: &str, asset_pattern: Option<&str>, out: &Messages) -> HashMap<DateTime<Utc>, PluginData> {
     // json parser -> https://docs.rs/serde_json/latest/serde_json/
-    let response_str: reqwest::Result<String> = response.text();
-    if response_str.is_err() {
-        println!("{}", "Failed to receive an API response.".red());
-        return HashMap::new()
-    };
     // hashmap -> key: plugin name, value: PluginData
-    let response_str: String = response_str.unwrap();
-    let parsed: serde_json::Result<serde_json::Value> = serde_json::from_str(response_str.as_str());
+    let parsed: serde_json::Result<serde_json::Value> = serde_json::from_str(response_str);
     if parsed.is_err() {
-        println!("{}", "Mapping failed to PluginData from the response data.".red());
+        out.line(format!("{}", "Mapping failed to PluginData from the response data.".red()));
         return HashMap::new()
     }
 
-    let parsed: Value = parsed.unwrap();
+    // '/releases/latest' and '/releases/tags/(tag)' return one release
+    let parsed: Value = match parsed.unwrap() {
+        Value::Object(release) => Value::Array(vec![Value::Object(release)]),
+        parsed => parsed,
+    };
 
     let mut unsorted_data: HashMap<DateTime<Utc>, PluginData> = HashMap::new();
 
-    for i in parsed.as_array() {
+    if let Some(i) = parsed.as_array() {
         for j in i.iter() {
-            let some_base: Vec<String> = j["html_url"].as_str().unwrap().split("/").map(|c| String::from(c)).collect();
-            let name: String = String::from(some_base.get(4).unwrap().as_str());
-            let version: String = String::from(some_base.get(7).unwrap().as_str());
-            let pre_release: bool = j["prerelease"].as_bool().unwrap();
-            let repository_url_base: Vec<String> = j["html_url"].to_string().split("/").map(|c| String::from(c)).collect();
-            let repository_url: String = format!("https://github.com/{}/{}", &repository_url_base[3], &repository_url_base[4]);
-            let mut file_name: String = String::new();
-            let mut created_date: String = String::new();
-            if j["assets"].as_array().unwrap().is_empty() { continue };
-            for k in j["assets"].as_array() {
-                file_name.push_str(k[0]["name"].as_str().unwrap());
-                created_date.push_str(k[0]["created_at"].as_str().unwrap());
-            }
-            let description: Option<Vec<String>> = if j["body"].as_str().is_some() { Some(vec![String::from(j["body"].as_str().unwrap().replace("\r\n", "\n"))]) } else { None };
-            let date: DateTime<Utc> = DateTime::parse_from_rfc3339(&created_date).unwrap().to_utc();
+            // (web url)/(UserName)/(RepositoryName)/releases/tag/(tag)
+            let html_url: &str = j["html_url"].as_str().unwrap_or("");
+            let repository_url: String = String::from(html_url.split_once("/releases/tag/").map_or(html_url, |(base, _)| base));
+            let name: String = String::from(repository_url.rsplit('/').next().unwrap());
+            let version: String = String::from(j["tag_name"].as_str().unwrap_or(""));
+            let pre_release: bool = j["prerelease"].as_bool().unwrap_or(false);
+            let mut assets: Vec<ReleaseAsset> = Vec::new();
+            if let Some(k) = j["assets"].as_array() {
+                for a in k {
+                    assets.push(ReleaseAsset {
+                        id: a["id"].as_u64().unwrap_or(0),
+                        name: String::from(a["name"].as_str().unwrap_or("")),
+                        download_url: String::from(a["browser_download_url"].as_str().unwrap_or("")),
+                        size: a["size"].as_u64().unwrap_or(0),
+                        digest: String::from(a["digest"].as_str().unwrap_or("")),
+                        checksum_url: String::new(),
+                        created_at: String::from(a["created_at"].as_str().unwrap_or("")),
+                    });
+                }
+            }
+            let candidates: Vec<ReleaseAsset> = select_assets(&assets, asset_pattern, out);
+            if candidates.is_empty() { continue };
+            let description: Option<Vec<String>> = if j["body"].as_str().is_some() { Some(vec![j["body"].as_str().unwrap().replace("\r\n", "\n")]) } else { None };
+            let date: Option<DateTime<Utc>> = DateTime::parse_from_rfc3339(&candidates[0].created_at).ok().map(|d| d.to_utc());
+            if date.is_none() { continue };
+            let date: DateTime<Utc> = date.unwrap();
             let key: DateTime<Utc> = date;
-            let plugin: PluginData = PluginData::new(name, version, date, description, pre_release, file_name, repository_url, true);
+            let mut plugin: PluginData = PluginData::new(name, version, date, description, pre_release, String::new(), repository_url, true);
+            plugin.apply_asset(&candidates[0]);
+            plugin.candidates = candidates;
 
             unsorted_data.insert(key, plugin);
         }
@@ -660,31 +3001,130 @@ fn response_parser (response: Response) -> HashMap<DateTime<Utc>, PluginData> {
     unsorted_data
 }
 
-fn get_latest_date(map: &HashMap<DateTime<Utc>, PluginData>) -> Option<DateTime<Utc>> {
-    let mut result: DateTime<Utc> = DateTime::<Utc>::MIN_UTC;
-    for v in map.keys() {
-        if v.signed_duration_since(result).num_seconds() > 0 {
-            result = *v;
+fn select_assets(assets: &[ReleaseAsset], pattern: Option<&str>, out: &Messages) -> Vec<ReleaseAsset> {
+    // with a pattern -> assets what match it
+    // without a pattern -> '.jar' assets except '-sources.jar' and '-javadoc.jar'
+    let matcher: Option<Regex> = pattern.and_then(asset_pattern_regex);
+    if let (Some(pattern), None) = (pattern, &matcher) {
+        out.line(format!("{} '{}'", "Failed to parse the asset pattern.".red(), pattern));
+        return Vec::new()
+    }
+    let mut selected: Vec<ReleaseAsset> = Vec::new();
+    for asset in assets {
+        let name: String = asset.name.to_lowercase();
+        let matched: bool = match &matcher {
+            Some(matcher) => matcher.is_match(&asset.name).unwrap_or(false),
+            None => name.ends_with(".jar") && !name.ends_with("-sources.jar") && !name.ends_with("-javadoc.jar"),
+        };
+        if !matched { continue };
+        let mut asset: ReleaseAsset = asset.clone();
+        // '(file name).sha256' or '(file name).sha512' that is published in the same release
+        let checksum_asset: Option<&ReleaseAsset> = assets.iter()
+            .find(|a| a.name == format!("{}.sha256", &asset.name) || a.name == format!("{}.sha512", &asset.name));
+        if let Some(checksum_asset) = checksum_asset {
+            asset.checksum_url = String::from(&checksum_asset.download_url);
         }
+        selected.push(asset);
     }
-    if result == DateTime::<Utc>::MIN_UTC { None } else { Some(result) }
+    // plain jars (e.g. 'plugin-1.0.jar') are preferred over classified ones (e.g. 'plugin-1.0-all.jar')
+    selected.sort_by_key(|a| a.name.len());
+    selected
 }
 
-fn print_api_error(cause: Option<String>) {
-    print!("{}", "[API ERROR] Failed to complete a task.".red());
-    if cause.is_some() { print!("Cause: {}", cause.unwrap()); }
-    stdout().flush().unwrap();
+fn asset_pattern_regex(pattern: &str) -> Option<Regex> {
+    // 'regex:(pattern)' -> regular expression, others -> glob ('*' and '?')
+    if let Some(regex) = pattern.strip_prefix("regex:") { return Regex::new(regex).ok() };
+    let mut regex: String = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            _ => regex.push_str(fancy_regex::escape(c.to_string().as_str()).as_ref()),
+        }
+    }
+    regex.push('$');
+    Regex::new(regex.as_str()).ok()
+}
+
+fn derive_asset_pattern(asset_name: &str, version: &str) -> String {
+    // 'Foo-Paper-1.2.3.jar' (v1.2.3) -> 'Foo-Paper-*.jar'
+    let version: &str = version.strip_prefix(['v', 'V']).unwrap_or(version);
+    if !version.is_empty() && asset_name.contains(version) {
+        return asset_name.replace(version, "*")
+    }
+    let digits: Regex = Regex::new(r"\d+(\.\d+)*").unwrap();
+    digits.replace_all(asset_name, "*").to_string()
+}
+
+fn pick_asset(plugin: &mut PluginData) {
+    // when the release has some installable assets, let the user pick one and remember it as a pattern.
+    if plugin.candidates.len() < 2 || plugin.asset_pattern.is_some() { return };
+    if !stdin().is_terminal() {
+        println!("{} {}", "Some assets are found. Selected:".yellow(), &plugin.file_name);
+        return
+    }
+    println!("{}", "The release has some assets. Which one do you want to install?".yellow());
+    for (i, asset) in plugin.candidates.iter().enumerate() {
+        println!(" {}: {}", i, &asset.name);
+    }
+    let select: String = prompt_input("mngr > register > asset > ");
+    let index: usize = select.parse::<usize>().ok().filter(|i| *i < plugin.candidates.len()).unwrap_or(0);
+    let asset: ReleaseAsset = plugin.candidates[index].clone();
+    plugin.apply_asset(&asset);
+    plugin.asset_pattern = Some(derive_asset_pattern(&asset.name, &plugin.version));
+    println!("{} {} ({})", "Selected:".green(), &asset.name, plugin.asset_pattern.as_ref().unwrap());
+}
+
+fn set_asset_pattern(app: &mut AppData, name: &str, pattern: Option<&str>) -> bool {
+    if !app.plugins.contains_key(name) {
+        println!("{} {}", "Not registered:".red(), name);
+        return false
+    }
+    if pattern.is_some_and(|p| asset_pattern_regex(p).is_none()) {
+        println!("{} '{}'", "Failed to parse the asset pattern.".red(), pattern.unwrap());
+        return false
+    }
+    app.plugins.get_mut(name).unwrap().asset_pattern = pattern.map(String::from);
+    match pattern {
+        Some(pattern) => println!("{} {} -> {}", "The asset pattern has been set.".green(), name, pattern),
+        None => println!("{} {}", "The asset pattern has been cleared.".green(), name),
+    }
+    true
 }
 
 fn show_help() {
-    println!("'{}' or '{}' - {}", "exit".green(), "E".green(), "exit from mngr interface.");
-    println!("'{}' or '{}' - {}", "help".green(), "H".green(), "show this page.");
-    println!("'{}' or '{}' - {}", "sync".green(), "S".green(), "sync with the plugins directory status.");
-    println!("'{}' or '{}' - {}", "register".green(), "R".green(), "Enter 'register' mode.");
-    println!("'{}' or '{}' - {}", "unregister".green(), "UR".green(), "Enter 'unregister' mode.");
-    println!("'{}' or '{}' - {}", "update".green(), "U".green(), "Enter 'update' mode.");
-    println!("'{}' or '{}' - {}", "list".green(), "L".green(), "displays all plugins info.");
-    println!("'{}' - {}", "remaining".green(), "displays remaining GitHub API request.");
+    println!("'{}' or '{}' - exit from mngr interface.", "exit".green(), "E".green());
+    println!("'{}' or '{}' - show this page.", "help".green(), "H".green());
+    println!("'{}' or '{}' - sync with the plugins directory status.", "sync".green(), "S".green());
+    println!("'{}' or '{}' - Enter 'register' mode.", "register".green(), "R".green());
+    println!("'{}' or '{}' - Enter 'unregister' mode.", "unregister".green(), "UR".green());
+    println!("'{}' or '{}' - Enter 'update' mode.", "update".green(), "U".green());
+    println!("'{}' or '{}' - Enter 'rollback' mode.", "rollback".green(), "RB".green());
+    println!("'{}' or '{}' - displays all plugins info.", "list".green(), "L".green());
+    println!("'{}' or '{}' - download registered plugins that are not placed and write 'mngr.lock'.", "install".green(), "I".green());
+    println!("'{}' or '{}' - check the dependencies of the installed plugins.", "deps".green(), "D".green());
+    println!("'{}' or '{}' - displays plugins what have newer releases.", "outdated".green(), "O".green());
+    println!("'{}' - displays remaining GitHub API request.", "remaining".green());
+}
+
+fn show_usage() {
+    println!("Usage: mngr [--offline] [command] (starts the interactive mode when no command is given)");
+    println!("  '--offline' answers from the cached API responses only, and sends no requests. (e.g. 'mngr --offline outdated')");
+    println!("  {} [((repository url) | modrinth:(project) | hangar:(owner)/(project) | spigot:(resource id) | jenkins:(job url) | gitlab:(project url) | gitea:(repository url) | actions:(repository url)#(branch)/(artifact))[@(tag)] | url:(link) | file:(path)] - register a plugin. (pinned to the tag when it is given)", "register".green());
+    println!("  {} [-n (plugin name) | -f (plugin file's name) | (plugin name)] - unregister a plugin.", "unregister".green());
+    println!("  {} [--all | --stable | (plugin name) ...] - update plugins. (pinned plugins are skipped)", "update".green());
+    println!("  {} (plugin name) --to (tag) - install the specified release and pin the plugin to it.", "update".green());
+    println!("  {} (plugin name) --unpin - unpin the plugin and update it to the latest release.", "update".green());
+    println!("  {} [--locked] - download registered plugins. ('--locked' reproduces 'mngr.lock' exactly)", "install".green());
+    println!("  {} (plugin name) [version] - restore the previous or the specified version.", "rollback".green());
+    println!("  {} (plugin name) [(constraint) | --clear] - set the version constraint for updates. (e.g. '^4.1', '<5')", "constraint".green());
+    println!("  {} (plugin name) [(pattern) | --clear] - set the asset pattern. (glob, or 'regex:(pattern)')", "asset".green());
+    println!("  {} - displays all plugins info.", "list".green());
+    println!("  {} [--download] [--remove-orphans] - sync with the plugins directory status.", "sync".green());
+    println!("  {} - check the dependencies ('depend') of the installed plugins.", "deps".green());
+    println!("  {} - displays plugins what have newer releases.", "outdated".green());
+    println!("  {} - displays remaining GitHub API request.", "rate".green());
+    println!("  {} - show this page.", "help".green());
 }
 
 fn print_plugins(app: &AppData) {
@@ -727,10 +3167,12 @@ fn create_config() -> Option<AppData> {
 fn get_config() -> Option<AppData> {
     let element: String = fs::read_to_string("mngr.toml").unwrap();
     let app: core::result::Result<AppData, toml::de::Error> = toml::from_str(element.as_str());
-    if app.is_ok() { Some(app.unwrap()) }
-    else {
-        println!("{}", "Failed to parse elements what are written in 'mngr.toml'.".red());
-        None
+    match app {
+        Ok(app) => Some(app),
+        Err(_) => {
+            println!("{}", "Failed to parse elements what are written in 'mngr.toml'.".red());
+            None
+        }
     }
 }
 
@@ -746,3 +3188,848 @@ fn get_config_path() -> Option<PathBuf> {
     Some(path)
 }
 
+fn get_lock_path() -> Option<PathBuf> {
+    let mut path: PathBuf = env::current_dir().ok()?;
+    path.push("mngr.lock");
+    Some(path)
+}
+
+fn get_lock() -> Option<LockFile> {
+    let path: PathBuf = get_lock_path()?;
+    if !path.exists() { return Some(LockFile::default()) };
+    let element: Result<String> = fs::read_to_string(path.as_path());
+    if element.is_err() {
+        println!("{}", "Failed to read 'mngr.lock'.".red());
+        return None
+    }
+    match toml::from_str(element.unwrap().as_str()) {
+        Ok(lock) => Some(lock),
+        Err(_) => {
+            println!("{}", "Failed to parse elements what are written in 'mngr.lock'.".red());
+            None
+        }
+    }
+}
+
+fn lock_update(lock: &LockFile) {
+    let path: Option<PathBuf> = get_lock_path();
+    if path.is_none() || fs::write(path.unwrap().as_path(), toml::to_string(lock).unwrap()).is_err() {
+        println!("{}", "Failed to handle 'mngr.lock'.".red());
+        println!("{}", "mngr will not save the resolved versions.".yellow());
+    }
+}
+
+fn lock_plugin(plugin: &PluginData, download_url: &str, content: &[u8]) {
+    let lock: Option<LockFile> = get_lock();
+    if lock.is_none() { return };
+    let mut lock: LockFile = lock.unwrap();
+    let locked: LockedPlugin = LockedPlugin {
+        name: String::from(&plugin.name),
+        tag: String::from(&plugin.version),
+        asset_id: plugin.asset_id,
+        file_name: String::from(&plugin.file_name),
+        repository_url: String::from(&plugin.repository_url),
+        download_url: String::from(download_url),
+        size: content.len() as u64,
+        sha256: sha256_hex(content),
+        source: plugin.source,
+        project_id: String::from(&plugin.project_id),
+    };
+    lock.plugins.insert(String::from(&plugin.name), locked);
+    lock_update(&lock);
+}
+
+fn unlock_plugin(file_name: &str) {
+    let lock: Option<LockFile> = get_lock();
+    if lock.is_none() { return };
+    let mut lock: LockFile = lock.unwrap();
+    let size: usize = lock.plugins.len();
+    lock.plugins.retain(|_, v| v.file_name != file_name);
+    if lock.plugins.len() != size { lock_update(&lock); }
+}
+
+fn refresh_metadata(app: &mut AppData) -> bool {
+    // the real plugin name (and so on) is written in the jar, not in the repository url.
+    // returns true when some jars are changed. (e.g. installed or updated)
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    if directory.is_none() { return false };
+    let directory: PathBuf = directory.unwrap();
+    let mut changed: bool = false;
+    for plugin in app.plugins.values_mut() {
+        let mut path: PathBuf = directory.clone();
+        path.push(&plugin.file_name);
+        if plugin.file_name.is_empty() || !path.is_file() { continue };
+        let metadata: Option<PluginMetadata> = read_metadata(&path);
+        if metadata != plugin.metadata {
+            plugin.metadata = metadata;
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn read_metadata(path: &Path) -> Option<PluginMetadata> {
+    let mut archive: ZipArchive<File> = ZipArchive::new(File::open(path).ok()?).ok()?;
+    read_descriptor(&mut archive, path.to_str().unwrap_or(""), &Messages::printed())
+}
+
+fn read_descriptor<R: Read + Seek>(archive: &mut ZipArchive<R>, origin: &str, out: &Messages) -> Option<PluginMetadata> {
+    // Bukkit / Spigot -> 'plugin.yml', Paper -> 'paper-plugin.yml', BungeeCord -> 'bungee.yml', Velocity -> 'velocity-plugin.json'
+    // 'plugin.yml' is preferred when a jar has both of it and 'paper-plugin.yml'.
+    for descriptor in ["plugin.yml", "paper-plugin.yml", "bungee.yml", "velocity-plugin.json"] {
+        let mut text: String = String::new();
+        if !archive.by_name(descriptor).is_ok_and(|mut entry| entry.read_to_string(&mut text).is_ok()) { continue };
+        let parsed: Option<Value> =
+            if descriptor.ends_with(".json") { serde_json::from_str(&text).ok() }
+            else { serde_yaml_ng::from_str(&text).ok() };
+        if parsed.is_none() {
+            out.line(format!("{} {} ({})", "Failed to parse".yellow(), descriptor, origin));
+            return None
+        }
+        return Some(parse_metadata(descriptor, &parsed.unwrap()))
+    }
+    None
+}
+
+fn parse_metadata(descriptor: &str, parsed: &Value) -> PluginMetadata {
+    let mut metadata: PluginMetadata = PluginMetadata {
+        descriptor: String::from(descriptor),
+        name: metadata_text(&parsed["name"]).unwrap_or_default(),
+        version: metadata_text(&parsed["version"]).unwrap_or_default(),
+        main: metadata_text(&parsed["main"]).unwrap_or_default(),
+        api_version: metadata_text(&parsed["api-version"]),
+        folia_supported: parsed["folia-supported"].as_bool().unwrap_or(false),
+        ..Default::default()
+    };
+    metadata.authors.extend(metadata_list(&parsed["author"]));
+    metadata.authors.extend(metadata_list(&parsed["authors"]));
+    match descriptor {
+        "plugin.yml" => {
+            metadata.depend = metadata_list(&parsed["depend"]);
+            metadata.softdepend = metadata_list(&parsed["softdepend"]);
+            metadata.loadbefore = metadata_list(&parsed["loadbefore"]);
+            metadata.provides = metadata_list(&parsed["provides"]);
+        },
+        "paper-plugin.yml" => {
+            // dependencies.server.(name): { load: BEFORE | AFTER | OMIT, required: true | false }
+            if let Some(server) = parsed["dependencies"]["server"].as_object() {
+                for (name, dependency) in server {
+                    if dependency["load"].as_str() == Some("AFTER") { metadata.loadbefore.push(String::from(name)) }
+                    else if dependency["required"].as_bool().unwrap_or(true) { metadata.depend.push(String::from(name)) }
+                    else { metadata.softdepend.push(String::from(name)) }
+                }
+            }
+            metadata.provides = metadata_list(&parsed["provides"]);
+        },
+        "bungee.yml" => {
+            metadata.depend = metadata_list(&parsed["depends"]);
+            metadata.softdepend = metadata_list(&parsed["softDepends"]);
+        },
+        _ => {
+            // velocity: the id is used to refer to a plugin
+            if let Some(id) = metadata_text(&parsed["id"]) {
+                if metadata.name.is_empty() { metadata.name = id } else { metadata.provides.push(id) }
+            }
+            for dependency in parsed["dependencies"].as_array().into_iter().flatten() {
+                let id: Option<String> = metadata_text(&dependency["id"]);
+                if id.is_none() { continue };
+                if dependency["optional"].as_bool().unwrap_or(false) { metadata.softdepend.push(id.unwrap()) }
+                else { metadata.depend.push(id.unwrap()) }
+            }
+        },
+    }
+    metadata
+}
+
+fn remove_incompatible(releases: &mut Releases, app: &AppData, out: &Messages) {
+    let size: usize = releases.len();
+    releases.retain(|_, release| get_incompatibility(release, &app.server).is_none());
+    if releases.len() != size {
+        out.line(format!("{} {}", "Skipped incompatible releases:".yellow(), size - releases.len()));
+    }
+}
+
+fn get_incompatibility(release: &PluginData, server: &ServerConfig) -> Option<String> {
+    // checked with the metadata of the source (e.g. 'game_versions' and 'loaders' of Modrinth)
+    if !server.game_version.is_empty() && !release.game_versions.is_empty()
+        && !release.game_versions.iter().any(|v| game_version_matches(v, &server.game_version)) {
+        return Some(format!("for {}, server: {}", release.game_versions.join(", "), &server.game_version))
+    }
+    let accepted: &[&str] = get_accepted_platforms(&server.platform);
+    if !accepted.is_empty() && !release.platforms.is_empty()
+        && !release.platforms.iter().any(|p| accepted.contains(&p.to_lowercase().as_str())) {
+        return Some(format!("for {}, server: {}", release.platforms.join(", "), &server.platform))
+    }
+    None
+}
+
+fn get_jar_incompatibility<R: Read + Seek>(archive: &mut ZipArchive<R>, origin: &str, server: &ServerConfig, out: &Messages) -> Option<String> {
+    // checked with the descriptors in the jar (a jar may have some of them for some platforms)
+    let platform: String = server.platform.to_lowercase();
+    let descriptors: &[&str] = match platform.as_str() {
+        "paper" | "purpur" | "folia" => &["plugin.yml", "paper-plugin.yml"],
+        "spigot" => &["plugin.yml"],
+        "velocity" => &["velocity-plugin.json"],
+        _ => &[],
+    };
+    if !descriptors.is_empty() && !descriptors.iter().any(|d| archive.index_for_name(d).is_some()) {
+        return Some(format!("no {}, server: {}", descriptors.join(" or "), &server.platform))
+    }
+    if platform == "velocity" { return None };
+    let metadata: PluginMetadata = read_descriptor(archive, origin, out)?;
+    if platform == "folia" && !metadata.folia_supported {
+        return Some(String::from("not 'folia-supported'"))
+    }
+    if let Some(api_version) = metadata.api_version.as_ref().filter(|_| !server.game_version.is_empty()) {
+        // 'api-version' newer than the server -> the plugin is built for a newer Minecraft version
+        if parse_game_version(api_version) > parse_game_version(&server.game_version) {
+            return Some(format!("api-version: {}, server: {}", api_version, &server.game_version))
+        }
+    }
+    None
+}
+
+fn get_accepted_platforms(platform: &str) -> &'static [&'static str] {
+    // loaders what the server can run (empty -> not checked)
+    match platform.to_lowercase().as_str() {
+        "paper" => &["paper", "spigot", "bukkit"],
+        "purpur" => &["purpur", "paper", "spigot", "bukkit"],
+        "folia" => &["folia"],
+        "spigot" => &["spigot", "bukkit"],
+        "velocity" => &["velocity"],
+        _ => &[],
+    }
+}
+
+fn game_version_matches(supported: &str, version: &str) -> bool {
+    // supported: '1.20.4' or a range like '1.20-1.20.4' (Hangar)
+    match supported.split_once('-') {
+        Some((from, to)) => {
+            let version: Vec<u32> = parse_game_version(version);
+            parse_game_version(from) <= version && version <= parse_game_version(to)
+        },
+        None => supported == version,
+    }
+}
+
+fn parse_game_version(version: &str) -> Vec<u32> {
+    // '1.20.4' -> [1, 20, 4] (trailing zeros are removed, so '1.20' equals '1.20.0')
+    let mut parsed: Vec<u32> = version.split('.').map_while(|n| n.trim().parse::<u32>().ok()).collect();
+    while parsed.last() == Some(&0) { parsed.pop(); }
+    parsed
+}
+
+fn metadata_text(value: &Value) -> Option<String> {
+    // 'version: 1.0' is parsed as a number
+    match value {
+        Value::String(text) => Some(String::from(text)),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        _ => None,
+    }
+}
+
+fn metadata_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(values) => values.iter().filter_map(metadata_text).collect(),
+        _ => metadata_text(value).into_iter().collect(),
+    }
+}
+
+fn sha256_hex(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+fn install(app: &mut AppData) -> bool {
+    // downloads registered plugins those are not placed, and locks what are already placed.
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    if directory.is_none() {
+        println!("{}", "Failed to get 'plugins' directory's path.".red());
+        return false
+    }
+    let directory: PathBuf = directory.unwrap();
+    let lock: Option<LockFile> = get_lock();
+    if lock.is_none() { return false };
+    let lock: LockFile = lock.unwrap();
+
+    let mut succeeded: bool = true;
+    let mut names: Vec<&String> = app.plugins.keys().collect();
+    names.sort();
+    for name in names {
+        let plugin: &PluginData = app.plugins.get(name).unwrap();
+        let mut path: PathBuf = directory.clone();
+        path.push(&plugin.file_name);
+        if !path.exists() {
+            println!("\nInstall Target = {}", &plugin.name.underline());
+            if !jar_download(plugin, None, app) { succeeded = false; }
+            continue
+        }
+        let locked: bool = lock.plugins.get(name).is_some_and(|l| l.file_name == plugin.file_name);
+        if locked { continue };
+        let download_url: Option<String> = get_download_url(plugin);
+        if download_url.is_none() {
+            println!("{} {} {}", "Not locked:".yellow(), &plugin.file_name, "(no download url is known)".yellow());
+            continue
+        }
+        let download_url: String = download_url.unwrap();
+        match fs::read(path.as_path()) {
+            Ok(content) => {
+                // the published checksums of GitHub Actions belong to the artifact archive, not to the jar in it
+                if plugin.source != SourceKind::Actions && !verify_checksum(plugin, &content, app, &Messages::printed()) {
+                    println!("{} {} {}", "Not locked:".red(), &plugin.file_name, "(the placed jar is not the registered release)".red());
+                    succeeded = false;
+                    continue
+                }
+                lock_plugin(plugin, &download_url, &content);
+                println!("{} {}", "Locked:".green(), &plugin.file_name);
+            },
+            Err(_) => {
+                println!("{} {}", "Failed to read the file.".red(), path.to_str().unwrap());
+                succeeded = false;
+            }
+        }
+    }
+    succeeded
+}
+
+fn install_locked(app: &mut AppData) -> bool {
+    // reproduces the plugins what are written in 'mngr.lock' without resolving the latest version.
+    let lock_path: Option<PathBuf> = get_lock_path();
+    if lock_path.is_none() || !lock_path.unwrap().exists() {
+        println!("{}", "'mngr.lock' is not found. Run 'install' without '--locked' first.".red());
+        return false
+    }
+    let lock: Option<LockFile> = get_lock();
+    if lock.is_none() { return false };
+    let lock: LockFile = lock.unwrap();
+    let directory: Option<PathBuf> = get_plugins_directory_path();
+    if directory.is_none() || fs::create_dir_all(directory.as_ref().unwrap()).is_err() {
+        println!("{}", "Failed to handle 'plugins' directory's path.".red());
+        return false
+    }
+    let directory: PathBuf = directory.unwrap();
+
+    let out: Messages = Messages::printed();
+    let mut succeeded: bool = true;
+    for locked in lock.plugins.values() {
+        let mut path: PathBuf = directory.clone();
+        path.push(&locked.file_name);
+        let placed: bool = fs::read(path.as_path()).is_ok_and(|c| sha256_hex(&c) == locked.sha256);
+        // the entry in mngr.toml is preferred ('mngr.lock' written by older versions has no source)
+        let source: SourceKind = app.plugins.get(&locked.name).map_or(locked.source, |pl| pl.source);
+        if !placed {
+            println!("\nInstall Target = {} ({})", &locked.name.underline(), &locked.tag);
+            let content: Option<Vec<u8>> = download_bytes(&locked.download_url, app, &out);
+            if content.is_none() {
+                if source == SourceKind::Actions {
+                    println!("{}", "Artifacts of GitHub Actions expire (90 days by default), so they may not be reproduced. Run 'update' to install a newer build.".yellow());
+                }
+                succeeded = false;
+                continue
+            }
+            let mut content: Vec<u8> = content.unwrap();
+            if source == SourceKind::Actions {
+                let asset_pattern: Option<&str> = app.plugins.get(&locked.name).and_then(|pl| pl.asset_pattern.as_deref());
+                let extracted: Option<Vec<u8>> = extract_jar(&content, asset_pattern, &out);
+                if extracted.is_none() {
+                    succeeded = false;
+                    continue
+                }
+                content = extracted.unwrap();
+            }
+            if content.len() as u64 != locked.size || sha256_hex(&content) != locked.sha256 {
+                println!("{} {}", "The downloaded file does not match 'mngr.lock'.".red(), &locked.file_name);
+                succeeded = false;
+                continue
+            }
+            if !place_jar(&locked.file_name, &content, app.plugins.get(&locked.name)) {
+                println!("{}", "Failed to save the downloaded content.".red());
+                succeeded = false;
+                continue
+            }
+            println!("{} '{}'", "Saved as".green(), path.to_str().unwrap());
+        }
+
+        let replaced: Option<PluginData> = app.plugins.get(&locked.name)
+            .filter(|pl| pl.version != locked.tag)
+            .cloned();
+        if let Some(replaced) = replaced { push_history(app, replaced); }
+        let plugin: &mut PluginData = app.plugins.entry(String::from(&locked.name))
+            .or_insert_with(|| {
+                let mut plugin: PluginData = PluginData::new(String::from(&locked.name), String::new(), Utc::now(), None, false, String::new(), String::from(&locked.repository_url), false);
+                plugin.source = locked.source;
+                plugin.project_id = String::from(&locked.project_id);
+                plugin
+            });
+        plugin.version = String::from(&locked.tag);
+        plugin.file_name = String::from(&locked.file_name);
+        plugin.asset_id = locked.asset_id;
+        plugin.download_url = String::from(&locked.download_url);
+        plugin.size = locked.size;
+    }
+
+    let status: Option<SyncStatus> = get_sync_status(app);
+    if let Some(status) = status {
+        for orphan in &status.orphans {
+            println!("{} {}", "Not in 'mngr.lock':".yellow(), orphan);
+        }
+    }
+    succeeded
+}
+
+fn download_bytes(url: &str, app: &AppData, out: &Messages) -> Option<Vec<u8>> {
+    if let Some(path) = url.strip_prefix("file://") {
+        return match fs::read(path) {
+            Ok(content) => Some(content),
+            Err(_) => {
+                out.line(format!("{} From: {}", "Failed to read a local file.".red(), path.underline()));
+                None
+            }
+        }
+    }
+    let mut builder: RequestBuilder = app.api.client.get(url).header("User-Agent", "mngr");
+    if let Some(forge) = get_forge(url, app).filter(|f| !f.token.is_empty()) {
+        builder = builder.header("Authorization", format!("Bearer {}", &forge.token));
+    } else if url.starts_with(app.github.api_url.trim_end_matches('/')) && !app.github_token.is_empty() {
+        // e.g. artifacts of GitHub Actions (redirected to the storage without the token)
+        builder = builder.header("Authorization", format!("token {}", &app.github_token));
+    }
+    let response: core::result::Result<Response, String> = send_request(builder, app, out);
+    if response.is_err() {
+        out.line(format!("{} From: {}", "Failed to download a release file.".red(), url.underline()));
+        return None
+    }
+    let response: Response = response.unwrap();
+    if !response.status().is_success() {
+        out.line(format!("{} Code: {} From: {}", "Failed to download a release file.".red(), response.status().as_u16(), url.underline()));
+        return None
+    }
+    match response.bytes() {
+        Ok(content) => Some(content.to_vec()),
+        Err(_) => {
+            out.line(format!("{} From: {}", "Failed to receive a release file.".red(), url.underline()));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader};
+    use std::net::{TcpListener, TcpStream};
+    use zip::write::SimpleFileOptions;
+
+    // '.mngr' and 'plugins' are relative to the current directory, what is shared by the tests
+    static CURRENT_DIR: Mutex<()> = Mutex::new(());
+
+    fn in_temp_dir<U>(task: impl FnOnce() -> U) -> U {
+        let _guard = CURRENT_DIR.lock().unwrap_or_else(|e| e.into_inner());
+        let directory: PathBuf = env::temp_dir().join(format!("mngr-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let previous: PathBuf = env::current_dir().unwrap();
+        env::set_current_dir(&directory).unwrap();
+        let result: U = task();
+        env::set_current_dir(previous).unwrap();
+        fs::remove_dir_all(&directory).ok();
+        result
+    }
+
+    fn serve(routes: impl Fn(&str, &str) -> Option<Vec<u8>> + Send + 'static) -> String {
+        // a local HTTP stand-in. routes: (base url, path) -> body (None -> 404)
+        // the ETag is the hash of the body, so 'If-None-Match' with it is answered with 304
+        let listener: TcpListener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base: String = format!("http://{}", listener.local_addr().unwrap());
+        let address: String = String::from(&base);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut stream: TcpStream = stream;
+                let mut reader: BufReader<TcpStream> = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line: String = String::new();
+                reader.read_line(&mut request_line).ok();
+                let mut if_none_match: String = String::new();
+                loop {
+                    let mut header: String = String::new();
+                    if reader.read_line(&mut header).unwrap_or(0) <= 2 { break };
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("If-None-Match") { if_none_match = String::from(value.trim()); }
+                    }
+                }
+                let target: &str = request_line.split_whitespace().nth(1).unwrap_or("/");
+                let path: &str = target.split('?').next().unwrap();
+                let body: Option<Vec<u8>> = routes(&address, path);
+                let etag: String = body.as_ref().map(|b| format!("\"{}\"", sha256_hex(b))).unwrap_or_default();
+                let (status, body): (&str, Vec<u8>) = match body {
+                    Some(_) if etag == if_none_match => ("304 Not Modified", Vec::new()),
+                    Some(body) => ("200 OK", body),
+                    None => ("404 Not Found", Vec::new()),
+                };
+                write!(stream, "HTTP/1.1 {}\r\nETag: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, etag, body.len()).ok();
+                stream.write_all(&body).ok();
+            }
+        });
+        base
+    }
+
+    fn build_jar(descriptor: &str) -> Vec<u8> {
+        let mut writer: zip::ZipWriter<Cursor<Vec<u8>>> = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        writer.start_file("plugin.yml", SimpleFileOptions::default()).unwrap();
+        writer.write_all(descriptor.as_bytes()).unwrap();
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn asset(name: &str) -> ReleaseAsset {
+        ReleaseAsset { name: String::from(name), download_url: format!("https://example.com/{}", name), ..Default::default() }
+    }
+
+    fn plugin(name: &str, version: &str, file_name: &str) -> PluginData {
+        PluginData::new(String::from(name), String::from(version), Utc::now(), None, false, String::from(file_name), String::new(), true)
+    }
+
+    #[test]
+    fn normalize_version_pads_tags() {
+        assert_eq!(normalize_version("v4.1"), "4.1.0");
+        assert_eq!(normalize_version("2-SNAPSHOT"), "2.0.0-SNAPSHOT");
+        assert_eq!(normalize_version("1.2.3-beta"), "1.2.3-beta");
+        assert_eq!(normalize_version("build-12"), "build-12");
+    }
+
+    #[test]
+    fn satisfies_constraint_compares_tags() {
+        assert!(satisfies_constraint("v4.1", "^4"));
+        assert!(satisfies_constraint("4.9.9", "<5.0"));
+        assert!(!satisfies_constraint("v5.0", "<5.0"));
+        assert!(satisfies_constraint("v1.0", "*"));
+        // a constraint without an operator is not accepted
+        assert!(!satisfies_constraint("1.0", "1.0"));
+        assert!(!is_older_version("12", "build-3"));
+        assert!(is_older_version("v1.9", "2.0"));
+    }
+
+    #[test]
+    fn select_assets_prefers_plain_jars() {
+        let assets: Vec<ReleaseAsset> = vec![
+            asset("plugin-1.0-all.jar"),
+            asset("plugin-1.0-sources.jar"),
+            asset("plugin-1.0.jar"),
+            asset("plugin-1.0.jar.sha256"),
+            asset("README.md"),
+        ];
+        let out: Messages = Messages::printed();
+        let selected: Vec<ReleaseAsset> = select_assets(&assets, None, &out);
+        let names: Vec<&str> = selected.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["plugin-1.0.jar", "plugin-1.0-all.jar"]);
+        assert_eq!(selected[0].checksum_url, "https://example.com/plugin-1.0.jar.sha256");
+
+        let selected: Vec<ReleaseAsset> = select_assets(&assets, Some("*-all.jar"), &out);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name, "plugin-1.0-all.jar");
+        assert!(select_assets(&assets, Some("regex:("), &out).is_empty());
+    }
+
+    #[test]
+    fn asset_pattern_regex_reads_globs() {
+        let glob: Regex = asset_pattern_regex("plugin-?.*.jar").unwrap();
+        assert!(glob.is_match("plugin-1.0.jar").unwrap());
+        assert!(!glob.is_match("plugin-10.jar.sha256").unwrap());
+        // '.' in a glob is not a wildcard
+        assert!(!asset_pattern_regex("a.jar").unwrap().is_match("abjar").unwrap());
+        let regex: Regex = asset_pattern_regex(r"regex:^plugin-\d+\.jar$").unwrap();
+        assert!(regex.is_match("plugin-12.jar").unwrap());
+        assert!(!regex.is_match("plugin-x.jar").unwrap());
+    }
+
+    #[test]
+    fn get_next_link_finds_next_page() {
+        let link: &str = r#"<https://api.github.com/repositories/1/releases?per_page=100&page=2>; rel="next", <https://api.github.com/repositories/1/releases?per_page=100&page=5>; rel="last""#;
+        assert_eq!(get_next_link(link).as_deref(), Some("https://api.github.com/repositories/1/releases?per_page=100&page=2"));
+        assert_eq!(get_next_link(r#"<https://api.github.com/repositories/1/releases?page=1>; rel="prev""#), None);
+        assert_eq!(get_next_link(""), None);
+    }
+
+    #[test]
+    fn parse_metadata_reads_descriptors() {
+        let plugin_yml: Value = serde_yaml_ng::from_str("name: Sample\nversion: 1.0\nmain: a.B\napi-version: '1.20'\nauthor: someone\ndepend: [Vault]\nsoftdepend: [PlaceholderAPI]\nfolia-supported: true\n").unwrap();
+        let metadata: PluginMetadata = parse_metadata("plugin.yml", &plugin_yml);
+        assert_eq!(metadata.name, "Sample");
+        assert_eq!(metadata.version, "1.0");
+        assert_eq!(metadata.api_version.as_deref(), Some("1.20"));
+        assert_eq!(metadata.authors, ["someone"]);
+        assert_eq!(metadata.depend, ["Vault"]);
+        assert_eq!(metadata.softdepend, ["PlaceholderAPI"]);
+        assert!(metadata.folia_supported);
+
+        let paper_yml: Value = serde_yaml_ng::from_str("name: Sample\nversion: '2'\nmain: a.B\ndependencies:\n  server:\n    Vault:\n      required: true\n    LuckPerms:\n      required: false\n    Early:\n      load: AFTER\n").unwrap();
+        let metadata: PluginMetadata = parse_metadata("paper-plugin.yml", &paper_yml);
+        assert_eq!(metadata.depend, ["Vault"]);
+        assert_eq!(metadata.softdepend, ["LuckPerms"]);
+        assert_eq!(metadata.loadbefore, ["Early"]);
+
+        let velocity: Value = serde_json::from_str(r#"{"id": "sample", "version": "3", "main": "a.B", "dependencies": [{"id": "luckperms", "optional": true}, {"id": "core"}]}"#).unwrap();
+        let metadata: PluginMetadata = parse_metadata("velocity-plugin.json", &velocity);
+        assert_eq!(metadata.name, "sample");
+        assert_eq!(metadata.depend, ["core"]);
+        assert_eq!(metadata.softdepend, ["luckperms"]);
+    }
+
+    #[test]
+    fn game_version_matches_ranges() {
+        assert_eq!(parse_game_version("1.20.4"), [1, 20, 4]);
+        assert_eq!(parse_game_version("1.20.0"), parse_game_version("1.20"));
+        assert!(game_version_matches("1.20.4", "1.20.4"));
+        assert!(!game_version_matches("1.20.4", "1.20.5"));
+        assert!(game_version_matches("1.20-1.20.4", "1.20.2"));
+        assert!(game_version_matches("1.20-1.20.4", "1.20"));
+        assert!(!game_version_matches("1.20-1.20.4", "1.21"));
+    }
+
+    #[test]
+    fn get_retry_wait_reads_rate_limit_headers() {
+        let app: AppData = AppData::new(None, None, None);
+        let headers = |pairs: &[(&'static str, String)]| -> HeaderMap {
+            let mut map: HeaderMap = HeaderMap::new();
+            for (name, value) in pairs { map.insert(*name, value.parse().unwrap()); }
+            map
+        };
+        let reset: String = (Utc::now().timestamp() + 30).to_string();
+        let limited: HeaderMap = headers(&[("X-RateLimit-Remaining", String::from("0")), ("X-RateLimit-Reset", reset)]);
+        // GitHub -> until the reset time
+        assert!(get_retry_wait(403, &limited, true, 0, &app).is_some_and(|wait| (29..=30).contains(&wait)));
+        // other services -> 'X-RateLimit-Reset' is not a timestamp, so backoff
+        let relative: HeaderMap = headers(&[("X-RateLimit-Remaining", String::from("0")), ("X-RateLimit-Reset", String::from("30"))]);
+        assert_eq!(get_retry_wait(429, &relative, false, 2, &app), Some(4));
+        assert_eq!(get_retry_wait(403, &relative, false, 0, &app), None);
+
+        assert_eq!(get_retry_wait(429, &headers(&[("Retry-After", String::from("7"))]), false, 0, &app), Some(7));
+        assert_eq!(get_retry_wait(503, &HeaderMap::new(), false, 1, &app), Some(2));
+        assert_eq!(get_retry_wait(404, &HeaderMap::new(), true, 0, &app), None);
+        // longer than 'max_wait' -> not retried
+        let later: HeaderMap = headers(&[("Retry-After", (app.github.max_wait + 1).to_string())]);
+        assert_eq!(get_retry_wait(429, &later, true, 0, &app), None);
+    }
+
+    #[test]
+    fn jenkins_releases_from_stand_in() {
+        let jar: Vec<u8> = build_jar("name: Dev\nversion: 11\nmain: a.B\n");
+        let served: Vec<u8> = jar.clone();
+        let base: String = serve(move |_, path| match path {
+            "/job/dev/api/json" => Some(br#"{"name": "dev", "builds": [
+                {"number": 12, "result": "FAILURE", "timestamp": 1760000100000, "artifacts": [{"fileName": "dev-12.jar", "relativePath": "build/libs/dev-12.jar"}]},
+                {"number": 11, "result": "SUCCESS", "timestamp": 1760000000000, "artifacts": [
+                    {"fileName": "dev-11-sources.jar", "relativePath": "build/libs/dev-11-sources.jar"},
+                    {"fileName": "dev-11.jar", "relativePath": "build/libs/dev-11.jar"}]}]}"#.to_vec()),
+            "/job/dev/11/artifact/build/libs/dev-11.jar" => Some(served.clone()),
+            _ => None,
+        });
+        let app: AppData = AppData::new(None, None, None);
+        let out: Messages = Messages::printed();
+        let job: String = format!("{}/job/dev/", base);
+        let mut releases: Releases = in_temp_dir(|| fetch_jenkins_releases(&job, None, &app, &out)).unwrap();
+        assert_eq!(releases.len(), 1);
+        let latest: PluginData = get_latest_plugin(&mut releases).unwrap();
+        assert_eq!(latest.version, "11");
+        assert_eq!(latest.source, SourceKind::Jenkins);
+        assert_eq!(latest.file_name, "dev-11.jar");
+        assert_eq!(latest.download_url, format!("{}/job/dev/11/artifact/build/libs/dev-11.jar", base));
+        assert_eq!(download_bytes(&latest.download_url, &app, &out), Some(jar));
+    }
+
+    #[test]
+    fn github_release_fetch_and_download_from_mock() {
+        // 'api_url' and 'web_url' point at the stand-in (GitHub Enterprise Server layout)
+        let jar: Vec<u8> = build_jar("name: Sample\nversion: 1.0\nmain: a.B\n");
+        let digest: String = format!("sha256:{}", sha256_hex(&jar));
+        let (served, published): (Vec<u8>, String) = (jar.clone(), digest.clone());
+        let base: String = serve(move |base, path| {
+            let release = |tag: &str, digest: &str| -> Vec<u8> {
+                serde_json::json!({
+                    "html_url": format!("{}/own/sample/releases/tag/{}", base, tag),
+                    "tag_name": tag,
+                    "prerelease": false,
+                    "body": "notes",
+                    "assets": [{
+                        "id": 1,
+                        "name": "sample-1.0.jar",
+                        "browser_download_url": format!("{}/own/sample/releases/download/{}/sample-1.0.jar", base, tag),
+                        "size": served.len(),
+                        "digest": digest,
+                        "created_at": "2026-01-01T00:00:00Z",
+                    }],
+                }).to_string().into_bytes()
+            };
+            match path {
+                "/api/v3/repos/own/sample/releases/latest" => Some(release("v1.0", &published)),
+                "/api/v3/repos/own/sample/releases/tags/v0.9" => Some(release("v0.9", "sha256:0000")),
+                "/own/sample/releases/download/v1.0/sample-1.0.jar" | "/own/sample/releases/download/v0.9/sample-1.0.jar" => Some(served.clone()),
+                _ => None,
+            }
+        });
+        let out: Messages = Messages::printed();
+        let mut app: AppData = AppData::new(None, None, None);
+        app.github.api_url = format!("{}/api/v3", base);
+        app.github.web_url = String::from(&base);
+        let repository: String = format!("{}/own/sample", base);
+
+        in_temp_dir(|| {
+            let mut releases: Releases = fetch_github_releases_for_register(&repository, None, &app).unwrap();
+            let latest: PluginData = get_latest_plugin(&mut releases).unwrap();
+            assert_eq!(latest.name, "sample");
+            assert_eq!(latest.version, "v1.0");
+            assert_eq!(latest.repository_url, repository);
+            assert_eq!(latest.file_name, "sample-1.0.jar");
+
+            let (download_url, staged) = prepare_jar(&latest, None, &app, &out).unwrap();
+            assert!(install_jar(&latest, None, &download_url, &staged));
+            assert!(!staged.exists());
+            assert_eq!(fs::read("plugins/sample-1.0.jar").unwrap(), jar);
+            let locked: LockedPlugin = get_lock().unwrap().plugins.remove("sample").unwrap();
+            assert_eq!(locked.tag, "v1.0");
+            assert_eq!(format!("sha256:{}", locked.sha256), digest);
+
+            // the digest of the asset does not match -> refused
+            let mut tagged: Releases = fetch_github_releases_for_register(&repository, Some("v0.9"), &app).unwrap();
+            let tagged: PluginData = get_latest_plugin(&mut tagged).unwrap();
+            assert!(prepare_jar(&tagged, None, &app, &out).is_none());
+            // a missing tag is an empty list, not an error
+            assert!(fetch_github_releases_for_register(&repository, Some("v9.9"), &app).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn place_jar_backs_up_and_replaces_the_previous_jar() {
+        let (old, new): (Vec<u8>, Vec<u8>) = (build_jar("name: A\nversion: 1.0\nmain: a.B\n"), build_jar("name: A\nversion: 2.0\nmain: a.B\n"));
+        in_temp_dir(|| {
+            fs::create_dir_all("plugins").unwrap();
+            fs::write("plugins/a-1.0.jar", &old).unwrap();
+            let previous: PluginData = plugin("A", "1.0", "a-1.0.jar");
+            assert!(place_jar("a-2.0.jar", &new, Some(&previous)));
+            assert_eq!(fs::read("plugins/a-2.0.jar").unwrap(), new);
+            assert!(!Path::new("plugins/a-1.0.jar").exists());
+            assert_eq!(fs::read(get_backup_path(&previous).unwrap()).unwrap(), old);
+            // no staged file is left
+            assert_eq!(fs::read_dir("plugins").unwrap().count(), 1);
+        });
+    }
+
+    #[test]
+    fn failed_downloads_keep_the_previous_jar() {
+        let old: Vec<u8> = build_jar("name: A\nversion: 1.0\nmain: a.B\n");
+        let base: String = serve(|_, _| None);
+        in_temp_dir(|| {
+            fs::create_dir_all("plugins").unwrap();
+            fs::write("plugins/a.jar", &old).unwrap();
+            let previous: PluginData = plugin("A", "1.0", "a.jar");
+            assert!(!place_jar("a.jar", b"not a jar", Some(&previous)));
+            let mut missing: PluginData = plugin("A", "2.0", "a.jar");
+            missing.download_url = format!("{}/a-2.0.jar", base);
+            assert!(!jar_download(&missing, Some(&previous), &AppData::new(None, None, None)));
+            assert_eq!(fs::read("plugins/a.jar").unwrap(), old);
+            assert_eq!(fs::read_dir("plugins").unwrap().count(), 1);
+        });
+    }
+
+    #[test]
+    fn push_history_forgets_the_oldest_backups() {
+        in_temp_dir(|| {
+            let mut app: AppData = AppData::new(None, None, None);
+            app.max_history = 2;
+            let versions: Vec<PluginData> = ["1.0", "2.0", "3.0"].iter().map(|v| plugin("A", v, "a.jar")).collect();
+            for pl in &versions {
+                let backup: PathBuf = get_backup_path(pl).unwrap();
+                fs::create_dir_all(backup.parent().unwrap()).unwrap();
+                fs::write(&backup, pl.version.as_bytes()).unwrap();
+                push_history(&mut app, pl.clone());
+            }
+            let kept: Vec<&str> = app.history["A"].iter().map(|pl| pl.version.as_str()).collect();
+            assert_eq!(kept, ["2.0", "3.0"]);
+            // the version directory of the expired backup is removed with it
+            assert!(!get_backup_path(&versions[0]).unwrap().parent().unwrap().exists());
+            assert!(get_backup_path(&versions[1]).unwrap().exists());
+        });
+    }
+
+    #[test]
+    fn rollback_restores_the_previous_jar() {
+        let (old, new): (Vec<u8>, Vec<u8>) = (build_jar("name: A\nversion: 1.0\nmain: a.B\n"), build_jar("name: A\nversion: 2.0\nmain: a.B\n"));
+        in_temp_dir(|| {
+            let mut app: AppData = AppData::new(None, None, None);
+            let (previous, current): (PluginData, PluginData) = (plugin("A", "1.0", "a-1.0.jar"), plugin("A", "2.0", "a-2.0.jar"));
+            let backup: PathBuf = get_backup_path(&previous).unwrap();
+            fs::create_dir_all(backup.parent().unwrap()).unwrap();
+            fs::write(&backup, &old).unwrap();
+            fs::create_dir_all("plugins").unwrap();
+            fs::write("plugins/a-2.0.jar", &new).unwrap();
+            app.plugins.insert(String::from("A"), current.clone());
+            app.history.insert(String::from("A"), vec![previous]);
+
+            assert!(rollback(&mut app, "A", None));
+            assert_eq!(fs::read("plugins/a-1.0.jar").unwrap(), old);
+            assert!(!Path::new("plugins/a-2.0.jar").exists());
+            assert_eq!(app.plugins["A"].version, "1.0");
+            assert_eq!(app.plugins["A"].pinned.as_deref(), Some("1.0"));
+            // the replaced version can be restored again
+            assert_eq!(app.history["A"].iter().map(|pl| pl.version.as_str()).collect::<Vec<&str>>(), ["2.0"]);
+            assert_eq!(fs::read(get_backup_path(&current).unwrap()).unwrap(), new);
+            assert!(!backup.exists());
+        });
+    }
+
+    #[test]
+    fn send_cached_reuses_not_modified_and_offline_responses() {
+        let base: String = serve(|_, path| (path == "/data").then(|| b"{\"version\": 1}".to_vec()));
+        in_temp_dir(|| {
+            let mut app: AppData = AppData::new(None, None, None);
+            let out: Messages = Messages::printed();
+            let url: String = format!("{}/data", base);
+            let first: CachedResponse = send_cached(app.api.client.get(&url), &app, &out).unwrap();
+            assert_eq!(first.status, 200);
+            assert!(!first.etag.is_empty());
+            let second: CachedResponse = send_cached(app.api.client.get(&url), &app, &out).unwrap();
+            assert_eq!((second.status, second.body.as_str()), (200, first.body.as_str()));
+            assert_eq!(app.api.usage.lock().unwrap().not_modified, 1);
+
+            app.offline = true;
+            assert_eq!(send_cached(app.api.client.get(&url), &app, &out).unwrap().body, first.body);
+            assert!(send_cached(app.api.client.get(format!("{}/other", base)), &app, &out).is_none());
+            assert!(send_request(app.api.client.get(&url), &app, &out).is_err());
+        });
+    }
+
+    #[test]
+    fn all_update_applies_each_outcome_to_its_plugin() {
+        // every job has one successful build, 'broken' is not served
+        let base: String = serve(|_, path| {
+            let (job, rest) = path.strip_prefix("/job/")?.split_once('/')?;
+            let latest: u64 = match job { "alpha" => 2, "beta" => 7, "gamma" => 4, _ => return None };
+            if rest == "api/json" {
+                return Some(serde_json::json!({"name": job, "builds": [{"number": latest, "result": "SUCCESS", "timestamp": 1760000000000u64,
+                    "artifacts": [{"fileName": format!("{}.jar", job), "relativePath": format!("{}.jar", job)}]}]}).to_string().into_bytes())
+            }
+            (rest == format!("{}/artifact/{}.jar", latest, job)).then(|| build_jar(&format!("name: {}\nversion: {}\nmain: a.B\n", job, latest)))
+        });
+        in_temp_dir(|| {
+            let mut app: AppData = AppData::new(None, None, None);
+            app.workers = 3;
+            fs::create_dir_all("plugins").unwrap();
+            let names: Vec<String> = ["alpha", "broken", "beta", "gamma"].map(String::from).to_vec();
+            for name in &names {
+                let mut installed: PluginData = plugin(name, "1", &format!("{}.jar", name));
+                installed.source = SourceKind::Jenkins;
+                installed.project_id = format!("{}/job/{}", base, name);
+                fs::write(format!("plugins/{}.jar", name), build_jar(&format!("name: {}\nversion: 1\nmain: a.B\n", name))).unwrap();
+                app.plugins.insert(String::from(name), installed);
+            }
+
+            assert!(!all_update(&names, &mut app));
+            let versions: Vec<&str> = names.iter().map(|name| app.plugins[name].version.as_str()).collect();
+            assert_eq!(versions, ["2", "1", "7", "4"]);
+            for name in &names {
+                // the jar in 'plugins' is the one of its entry
+                let metadata: PluginMetadata = read_metadata(Path::new(&format!("plugins/{}.jar", name))).unwrap();
+                assert_eq!(metadata.version, app.plugins[name].version);
+            }
+            assert!(!app.history.contains_key("broken"));
+            assert_eq!(app.history["gamma"][0].version, "1");
+        });
+    }
+}